@@ -0,0 +1,203 @@
+//! Module of the in-process caches of the IDs of the players, openings, rulesets and final configurations, which spare the database a query for every value that was already seen.
+
+use std::{borrow::Borrow, hash::Hash, num::NonZeroUsize};
+
+use log::info;
+use lru::LruCache;
+
+use lichess::attributes::BoardConfiguration;
+
+/// Default maximum number of IDs kept by each cache.
+pub const DEFAULT_CACHE_SIZE: usize = 1 << 20;
+
+/// A least recently used cache of the database IDs of the values of a table, which counts its hits and misses.
+#[derive(Debug)]
+pub struct Cache<K: Hash + Eq> {
+    /// IDs of the values, or [`None`] if caching is disabled.
+    ids: Option<LruCache<K, u64>>,
+    /// Number of lookups that found the ID.
+    hits: u64,
+    /// Number of lookups that didn't find the ID.
+    misses: u64,
+}
+
+impl<K: Hash + Eq> Cache<K> {
+    /// Creates a cache that keeps at most `capacity` IDs, which is disabled if the capacity is 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ids: NonZeroUsize::new(capacity).map(LruCache::new),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Gets the ID of a value, if it is cached.
+    pub fn get<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+    {
+        let id = self.ids.as_mut()?.get(key).copied();
+        if id.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        id
+    }
+
+    /// Caches the ID of a value.
+    pub fn insert(&mut self, key: K, id: u64) {
+        if let Some(ids) = &mut self.ids {
+            ids.put(key, id);
+        }
+    }
+
+    /// Gets the ID of a value if it is cached, getting it from `insert` and caching it otherwise.
+    ///
+    /// # Errors
+    /// Will return `E` if `insert` fails.
+    #[cfg_attr(
+        not(any(feature = "time-mysql", feature = "chrono-mysql")),
+        allow(dead_code)
+    )]
+    pub fn get_or_insert<Q: Hash + Eq + ToOwned<Owned = K> + ?Sized, E>(
+        &mut self,
+        key: &Q,
+        insert: impl FnOnce() -> Result<u64, E>,
+    ) -> Result<u64, E>
+    where
+        K: Borrow<Q>,
+    {
+        if let Some(id) = self.get(key) {
+            return Ok(id);
+        }
+        let id = insert()?;
+        self.insert(key.to_owned(), id);
+        Ok(id)
+    }
+
+    /// Number of IDs cached.
+    pub fn len(&self) -> usize {
+        self.ids.as_ref().map_or(0, LruCache::len)
+    }
+
+    /// Forgets every ID, keeping the counts of hits and misses.
+    #[cfg_attr(
+        not(any(
+            feature = "time-mysql",
+            feature = "chrono-mysql",
+            feature = "time-sqlite",
+            feature = "chrono-sqlite"
+        )),
+        allow(dead_code)
+    )]
+    pub fn clear(&mut self) {
+        if let Some(ids) = &mut self.ids {
+            ids.clear();
+        }
+    }
+
+    /// Ratio of the lookups that found the ID, or [`None`] if there were none.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups != 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// The caches of the IDs of every table whose values are shared between games.
+#[derive(Debug)]
+pub struct IdCaches {
+    /// IDs of the players by their name.
+    pub players: Cache<String>,
+    /// IDs of the openings by their name.
+    pub openings: Cache<String>,
+    /// IDs of the rulesets by their name and url id.
+    pub rulesets: Cache<(String, String)>,
+    /// IDs of the final configurations.
+    pub final_configurations: Cache<BoardConfiguration>,
+}
+
+impl Default for IdCaches {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_SIZE)
+    }
+}
+
+impl IdCaches {
+    /// Creates the caches, each keeping at most `capacity` IDs, which are disabled if the capacity is 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            players: Cache::new(capacity),
+            openings: Cache::new(capacity),
+            rulesets: Cache::new(capacity),
+            final_configurations: Cache::new(capacity),
+        }
+    }
+
+    /// Forgets every ID, which must be done when the values inserted since they were cached are rolled back.
+    #[cfg_attr(
+        not(any(
+            feature = "time-mysql",
+            feature = "chrono-mysql",
+            feature = "time-sqlite",
+            feature = "chrono-sqlite"
+        )),
+        allow(dead_code)
+    )]
+    pub fn clear(&mut self) {
+        self.players.clear();
+        self.openings.clear();
+        self.rulesets.clear();
+        self.final_configurations.clear();
+    }
+
+    /// Logs the number of IDs cached.
+    pub fn log_sizes(&self) {
+        info!(
+            "Cached the IDs of {} players, {} openings, {} rulesets and {} final configurations.",
+            self.players.len(),
+            self.openings.len(),
+            self.rulesets.len(),
+            self.final_configurations.len()
+        );
+    }
+
+    /// Logs the hit rate of every cache.
+    pub fn log_hit_rates(&self) {
+        for (table, hit_rate) in [
+            ("Player", self.players.hit_rate()),
+            ("Opening", self.openings.hit_rate()),
+            ("RuleSet", self.rulesets.hit_rate()),
+            ("FinalConfiguration", self.final_configurations.hit_rate()),
+        ] {
+            if let Some(hit_rate) = hit_rate {
+                info!("{table} cache hit rate: {:.2}%.", hit_rate * 100.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Cache;
+
+    /// Tests the eviction of the least recently used IDs and the counting of hits and misses.
+    #[test]
+    fn cache_test() {
+        let mut cache = Cache::<String>::new(2);
+        assert_eq!(cache.get_or_insert("a", || Ok::<_, ()>(1)), Ok(1));
+        assert_eq!(cache.get_or_insert("b", || Ok::<_, ()>(2)), Ok(2));
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get_or_insert("c", || Ok::<_, ()>(3)), Ok(3));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get_or_insert("a", || Err(())), Ok(1));
+        assert_eq!(cache.hit_rate(), Some(2.0 / 6.0));
+
+        let mut disabled = Cache::<String>::new(0);
+        assert_eq!(disabled.get_or_insert("a", || Ok::<_, ()>(1)), Ok(1));
+        assert_eq!(disabled.get("a"), None);
+        assert_eq!(disabled.hit_rate(), None);
+    }
+}
@@ -0,0 +1,432 @@
+//! Module that exports the data to CSV files with the layout of the database tables, assigning the IDs client-side so that they can be bulk loaded into the database afterwards.
+
+use std::{
+    collections::HashMap,
+    fs::{File, create_dir_all},
+    io,
+    path::Path,
+};
+
+use csv::{Terminator, Writer, WriterBuilder};
+use log::{info, trace};
+
+use super::variant;
+use lichess::{
+    attributes::{BoardConfiguration, Eco, Eval, Opening, Player, RuleSet, datetime::Datetime},
+    data::{Data, Game, Move},
+};
+
+/// Name of the CSV file of the Player table.
+pub const PLAYER_CSV: &str = "Player.csv";
+/// Name of the CSV file of the Opening table.
+pub const OPENING_CSV: &str = "Opening.csv";
+/// Name of the CSV file of the RuleSet table.
+pub const RULESET_CSV: &str = "RuleSet.csv";
+/// Name of the CSV file of the FinalConfiguration table.
+pub const FINAL_CONFIGURATION_CSV: &str = "FinalConfiguration.csv";
+/// Name of the CSV file of the Game table.
+pub const GAME_CSV: &str = "Game.csv";
+/// Name of the CSV file of the Move table.
+pub const MOVE_CSV: &str = "Move.csv";
+
+/// Formats an optional value as a CSV field, which is empty when there is no value.
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// An exporter of the data to a directory of CSV files, one per table of the database.
+#[derive(Debug)]
+pub struct CsvExport {
+    /// Writer of the Player table.
+    players: Writer<File>,
+    /// Writer of the Opening table.
+    openings: Writer<File>,
+    /// Writer of the RuleSet table.
+    rulesets: Writer<File>,
+    /// Writer of the FinalConfiguration table.
+    final_configurations: Writer<File>,
+    /// Writer of the Game table.
+    games: Writer<File>,
+    /// Writer of the Move table.
+    moves: Writer<File>,
+    /// IDs assigned to the players by their name.
+    player_ids: HashMap<String, u64>,
+    /// IDs assigned to the openings by their name.
+    opening_ids: HashMap<String, u64>,
+    /// IDs assigned to the rulesets by their name and url id.
+    ruleset_ids: HashMap<(String, String), u64>,
+    /// IDs assigned to the final configurations.
+    final_configuration_ids: HashMap<BoardConfiguration, u64>,
+    /// ID of the last game written.
+    game_id: u64,
+}
+
+impl CsvExport {
+    /// Creates the CSV file of a table in the directory with its header.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the file could not be created or the header could not be written.
+    fn create_table(dir: &Path, name: &str, header: &[&str]) -> csv::Result<Writer<File>> {
+        let mut writer = WriterBuilder::new()
+            .terminator(Terminator::Any(b'\n'))
+            .from_path(dir.join(name))?;
+        writer.write_record(header)?;
+        Ok(writer)
+    }
+
+    /// Creates the directory, if it doesn't exist, and the CSV files of every table in it.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the directory or any of the files could not be created.
+    pub fn create<P: AsRef<Path>>(dir: P) -> csv::Result<Self> {
+        trace!("CsvExport create function.");
+        let dir = dir.as_ref();
+        info!("Creating the CSV files in {dir:?}.");
+        create_dir_all(dir)?;
+        Ok(Self {
+            players: Self::create_table(dir, PLAYER_CSV, &["PlayerId", "Name"])?,
+            openings: Self::create_table(
+                dir,
+                OPENING_CSV,
+                &["OpeningId", "Name", "EcoLetter", "EcoNumber"],
+            )?,
+            rulesets: Self::create_table(
+                dir,
+                RULESET_CSV,
+                &["RuleSetId", "Name", "URLId", "Kind"],
+            )?,
+            final_configurations: Self::create_table(
+                dir,
+                FINAL_CONFIGURATION_CSV,
+                &[
+                    "FCId",
+                    "EndBlackPieces",
+                    "EndWhitePieces",
+                    "ARow",
+                    "BRow",
+                    "CRow",
+                    "DRow",
+                    "ERow",
+                    "FRow",
+                    "GRow",
+                    "HRow",
+                ],
+            )?,
+            games: Self::create_table(
+                dir,
+                GAME_CSV,
+                &[
+                    "GameId",
+                    "RuleSetId",
+                    "OpeningId",
+                    "FCId",
+                    "White",
+                    "WhiteElo",
+                    "WhiteTitle",
+                    "Black",
+                    "BlackElo",
+                    "BlackTitle",
+                    "StartTime",
+                    "Increment",
+                    "Result",
+                    "Termination",
+                    "DateTime",
+                    "HasClock",
+                    "HasEvaluations",
+                ],
+            )?,
+            moves: Self::create_table(
+                dir,
+                MOVE_CSV,
+                &[
+                    "GameId",
+                    "Num",
+                    "Descriptor",
+                    "EvalFloat",
+                    "EvalInt",
+                    "Clock",
+                ],
+            )?,
+            player_ids: HashMap::new(),
+            opening_ids: HashMap::new(),
+            ruleset_ids: HashMap::new(),
+            final_configuration_ids: HashMap::new(),
+            game_id: 0,
+        })
+    }
+
+    /// Gets the ID of a [`Player`], writing it to the Player table if it is new.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the writing fails.
+    fn player_id(&mut self, player: &Player) -> csv::Result<u64> {
+        if let Some(id) = self.player_ids.get(&player.0) {
+            return Ok(*id);
+        }
+        let id = self.player_ids.len() as u64 + 1;
+        self.players
+            .write_record([id.to_string().as_str(), &player.0])?;
+        self.player_ids.insert(player.0.clone(), id);
+        Ok(id)
+    }
+
+    /// Gets the ID of an [`Opening`], writing it to the Opening table if it is new.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the writing fails.
+    fn opening_id(&mut self, opening: &Opening, eco: Eco) -> csv::Result<u64> {
+        if let Some(id) = self.opening_ids.get(&opening.0) {
+            return Ok(*id);
+        }
+        let id = self.opening_ids.len() as u64 + 1;
+        self.openings.write_record([
+            id.to_string(),
+            opening.0.clone(),
+            variant(eco.0),
+            eco.1.get().to_string(),
+        ])?;
+        self.opening_ids.insert(opening.0.clone(), id);
+        Ok(id)
+    }
+
+    /// Gets the ID of a [`RuleSet`], writing it to the RuleSet table if it is new.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the writing fails.
+    fn ruleset_id(&mut self, ruleset: &RuleSet) -> csv::Result<u64> {
+        let key = (ruleset.name.clone(), ruleset.url.clone());
+        if let Some(id) = self.ruleset_ids.get(&key) {
+            return Ok(*id);
+        }
+        let id = self.ruleset_ids.len() as u64 + 1;
+        self.rulesets.write_record([
+            id.to_string(),
+            ruleset.name.clone(),
+            ruleset.url.clone(),
+            variant(ruleset.kind),
+        ])?;
+        self.ruleset_ids.insert(key, id);
+        Ok(id)
+    }
+
+    /// Gets the ID of a [`BoardConfiguration`], writing it to the FinalConfiguration table if it is new.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the writing fails.
+    fn final_configuration_id(
+        &mut self,
+        final_configuration: &BoardConfiguration,
+    ) -> csv::Result<u64> {
+        if let Some(id) = self.final_configuration_ids.get(final_configuration) {
+            return Ok(*id);
+        }
+        let id = self.final_configuration_ids.len() as u64 + 1;
+        let mut record = vec![
+            id.to_string(),
+            final_configuration.black_left.to_string(),
+            final_configuration.white_left.to_string(),
+        ];
+        record.extend(final_configuration.rows.iter().map(u32::to_string));
+        self.final_configurations.write_record(record)?;
+        self.final_configuration_ids
+            .insert(*final_configuration, id);
+        Ok(id)
+    }
+
+    /// Writes a [`Game`]'s data into the Game, RuleSet, Opening, FinalConfiguration and Player tables.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if any of the writings fail.
+    fn write_game(&mut self, game: &Game) -> csv::Result<u64> {
+        let ruleset_id = self.ruleset_id(&game.ruleset)?;
+        let opening_id = if game.opening.0.is_empty() {
+            None
+        } else {
+            Some(self.opening_id(&game.opening, game.eco)?)
+        };
+        let fc_id = self.final_configuration_id(&game.final_conf)?;
+        let white_id = if game.white.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.white)?)
+        };
+        let black_id = if game.black.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.black)?)
+        };
+        let (start_time, increment) = game.time_control.0.unzip();
+        self.game_id += 1;
+        self.games.write_record([
+            self.game_id.to_string(),
+            ruleset_id.to_string(),
+            optional(opening_id),
+            fc_id.to_string(),
+            optional(white_id),
+            optional(game.white_elo.0),
+            optional(game.white_title.map(variant)),
+            optional(black_id),
+            optional(game.black_elo.0),
+            optional(game.black_title.map(variant)),
+            optional(start_time),
+            optional(increment),
+            variant(game.result),
+            variant(game.termination),
+            Datetime::new(game.utc_date.0, game.utc_time.0).to_string(),
+            u8::from(game.has_clock).to_string(),
+            u8::from(game.has_evaluations).to_string(),
+        ])?;
+        Ok(self.game_id)
+    }
+
+    /// Writes a [`Move`] into the Move table.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the writing fails.
+    fn write_move(&mut self, r#move: &Move, game_id: u64) -> csv::Result<()> {
+        let (eval_float, eval_int) = match r#move.eval {
+            Some(Eval::Numeric(num)) => (Some(num), None),
+            Some(Eval::Checkmate(checkmate)) => (None, Some(checkmate)),
+            None => (None, None),
+        };
+        self.moves.write_record([
+            game_id.to_string(),
+            r#move.num.to_string(),
+            r#move.descriptor.to_u32().to_string(),
+            optional(eval_float),
+            optional(eval_int),
+            optional(r#move.clk.map(|clk| clk.0)),
+        ])
+    }
+
+    /// Writes a [`Data`]'s [`Game`] and [`Vec`] of [`Move`]s into the Game, RuleSet, Opening, FinalConfiguration, Player and Move tables.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if any of the writings fail.
+    pub fn write_all(&mut self, data: &Data) -> csv::Result<()> {
+        let game_id = self.write_game(&data.game)?;
+        for r#move in &data.moves {
+            self.write_move(r#move, game_id)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes every CSV file.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if any of the files could not be flushed.
+    pub fn flush(&mut self) -> io::Result<()> {
+        trace!("CsvExport flush function.");
+        self.players.flush()?;
+        self.openings.flush()?;
+        self.rulesets.flush()?;
+        self.final_configurations.flush()?;
+        self.games.flush()?;
+        self.moves.flush()?;
+        info!(
+            "Exported {} games, {} players, {} openings, {} rulesets and {} final configurations.",
+            self.game_id,
+            self.player_ids.len(),
+            self.opening_ids.len(),
+            self.ruleset_ids.len(),
+            self.final_configuration_ids.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::remove_dir_all, path::Path};
+
+    use csv::ReaderBuilder;
+    use lichess::{attributes::attribute::StringAttribute, data::Data};
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        CsvExport, FINAL_CONFIGURATION_CSV, GAME_CSV, MOVE_CSV, OPENING_CSV, PLAYER_CSV,
+        RULESET_CSV,
+    };
+
+    /// Reads the rows of a CSV file, header included.
+    fn rows(dir: &Path, name: &str) -> Vec<Vec<String>> {
+        ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(dir.join(name))
+            .unwrap()
+            .records()
+            .map(|record| record.unwrap().iter().map(String::from).collect())
+            .collect()
+    }
+
+    /// Gets the columns a LOAD DATA statement loads the fields into, in order.
+    fn columns(sql: &str) -> Vec<String> {
+        let (_, columns) = sql.split_once("LINES\n(").unwrap();
+        let (columns, _) = columns.split_once(')').unwrap();
+        columns
+            .split(", ")
+            .map(|column| column.trim_start_matches('@').to_owned())
+            .collect()
+    }
+
+    /// Exports two games that share a player and an opening, checking that every table has the columns LOAD DATA expects and that the games reference the IDs assigned to them.
+    #[test]
+    fn csv_export_test() {
+        let dir = std::env::temp_dir().join("lichess-csv-test");
+        let mut export = CsvExport::create(&dir).unwrap();
+        let mut data = Data::default();
+        data.game.white.fill("Abbot");
+        data.game.black.fill("Costello");
+        data.game.opening.fill("Sicilian Defense");
+        data.game.eco = "B20".parse().unwrap();
+        data.moves.push(data.r#move.clone());
+        export.write_all(&data).unwrap();
+        data.game.white.clear();
+        data.game.white.fill("Costello");
+        data.game.black.clear();
+        data.game.black.fill("Dana");
+        data.moves.push(data.r#move.clone());
+        export.write_all(&data).unwrap();
+        export.flush().unwrap();
+
+        for (name, sql) in [
+            (PLAYER_CSV, include_str!("../sql/load-player.sql")),
+            (OPENING_CSV, include_str!("../sql/load-opening.sql")),
+            (RULESET_CSV, include_str!("../sql/load-ruleset.sql")),
+            (
+                FINAL_CONFIGURATION_CSV,
+                include_str!("../sql/load-finalconf.sql"),
+            ),
+            (GAME_CSV, include_str!("../sql/load-game.sql")),
+            (MOVE_CSV, include_str!("../sql/load-move.sql")),
+        ] {
+            assert_eq!(rows(&dir, name)[0], columns(sql), "{name}");
+        }
+        assert_eq!(
+            rows(&dir, PLAYER_CSV)[1..],
+            [["1", "Abbot"], ["2", "Costello"], ["3", "Dana"]]
+        );
+        assert_eq!(
+            rows(&dir, OPENING_CSV)[1..],
+            [["1", "Sicilian Defense", "B", "20"]]
+        );
+        assert_eq!(rows(&dir, RULESET_CSV).len(), 2);
+        assert_eq!(rows(&dir, FINAL_CONFIGURATION_CSV).len(), 2);
+        let games = rows(&dir, GAME_CSV);
+        let ids = |row: &Vec<String>| {
+            [0, 1, 2, 3, 4, 7]
+                .map(|column| row[column].as_str())
+                .join(",")
+        };
+        assert_eq!(games.len(), 3);
+        assert_eq!(ids(&games[1]), "1,1,1,1,1,2");
+        assert_eq!(ids(&games[2]), "2,1,1,1,2,3");
+        let moves = rows(&dir, MOVE_CSV)
+            .into_iter()
+            .skip(1)
+            .map(|row| row[0].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(moves, ["1", "2", "2"]);
+        remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,761 @@
+//! Module of the Diesel adapter, which inserts into the same MySQL schema as the mysql adapter through Diesel's typed queries, without table prefixes.
+
+use std::{
+    fmt::{self, Debug, Display},
+    num::TryFromIntError,
+};
+
+use diesel::{
+    Connection as _, ConnectionError, MysqlConnection,
+    connection::{AnsiTransactionManager, SimpleConnection, TransactionManager},
+    define_sql_function,
+    dsl::{self, insert_into},
+    prelude::*,
+    result::{DatabaseErrorKind, Error as QueryError},
+    sql_query,
+    sql_types::{BigInt, Bool, Datetime as SqlDatetime, Integer, Nullable, Text},
+};
+use log::{info, trace, warn};
+
+use lichess::{
+    attributes::{BoardConfiguration, Eco, Eval, Opening, Player, RuleSet, datetime::Datetime},
+    data::{Data, Game, Move, packed::pack},
+};
+
+use super::{
+    Connection, DatabaseAdapter, IdCaches, IngestionRun, MYSQL_MIGRATIONS, MoveStorage, Names,
+    Table,
+    schema::{
+        final_configuration, game, ingestion_run, r#move, opening, player, ruleset, schema_version,
+    },
+    variant,
+};
+
+define_sql_function! {
+    /// The MySQL `LAST_INSERT_ID()` function, which returns the id of the last row inserted by the connection.
+    fn last_insert_id() -> Unsigned<BigInt>;
+}
+
+/// A constraint of the Game or Move tables, as it is found in the information schema.
+#[derive(QueryableByName)]
+struct Constraint {
+    /// Table of the constraint.
+    #[diesel(sql_type = Text, column_name = TABLE_NAME)]
+    table: String,
+    /// Name of the constraint or of its index.
+    #[diesel(sql_type = Text, column_name = CONSTRAINT_NAME)]
+    name: String,
+}
+
+/// The number of rows that violate a constraint.
+#[derive(QueryableByName)]
+struct Violation {
+    /// Name of the constraint.
+    #[diesel(sql_type = Text, column_name = ConstraintName)]
+    name: String,
+    /// Number of rows that violate it.
+    #[diesel(sql_type = BigInt, column_name = Violations)]
+    rows: i64,
+}
+
+/// An error of the Diesel adapter.
+#[derive(Debug)]
+pub enum Error {
+    /// The connection to the database could not be established.
+    Connection(ConnectionError),
+    /// A query failed.
+    Query(QueryError),
+    /// An insertion failed, along with a description of the value that was being inserted.
+    Insertion(QueryError, String),
+    /// A prefix was given for the tables, whose names are fixed by the Diesel schema.
+    TablePrefix(String),
+    /// A query whose columns are only known when it runs, which Diesel can't load.
+    DynamicQuery,
+    /// An ID or a count that doesn't fit in the column it is stored in.
+    OutOfRange(TryFromIntError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connection(e) => write!(f, "{e}"),
+            Self::Query(e) => write!(f, "{e}"),
+            Self::Insertion(e, context) => write!(f, "{e} ({context})"),
+            Self::TablePrefix(prefix) => write!(
+                f,
+                "The table prefix {prefix:?} can't be used with Diesel, whose table names are fixed by its schema."
+            ),
+            Self::DynamicQuery => write!(
+                f,
+                "Diesel can't run queries whose columns are only known when they run, such as the reports. Please use the mysql feature instead."
+            ),
+            Self::OutOfRange(e) => write!(f, "A value doesn't fit in its column: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connection(e) => Some(e),
+            Self::Query(e) | Self::Insertion(e, _) => Some(e),
+            Self::OutOfRange(e) => Some(e),
+            Self::TablePrefix(_) | Self::DynamicQuery => None,
+        }
+    }
+}
+
+impl From<ConnectionError> for Error {
+    fn from(value: ConnectionError) -> Self {
+        Self::Connection(value)
+    }
+}
+
+impl From<TryFromIntError> for Error {
+    fn from(value: TryFromIntError) -> Self {
+        Self::OutOfRange(value)
+    }
+}
+
+impl From<QueryError> for Error {
+    fn from(value: QueryError) -> Self {
+        Self::Query(value)
+    }
+}
+
+impl Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection").finish_non_exhaustive()
+    }
+}
+
+/// Whether the error is caused by the violation of a unique key, meaning the value already existed.
+const fn is_duplicate(error: &QueryError) -> bool {
+    matches!(
+        error,
+        QueryError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)
+    )
+}
+
+impl Connection {
+    /// Runs insertions in a transaction through Diesel's transaction manager, so that either all of them are kept or none, since [`diesel::Connection::transaction`] would only lend the connection and not the caches. The IDs cached are cleared if the transaction is rolled back, since they may not exist anymore.
+    ///
+    /// # Errors
+    /// Will return [`Error`] if the insertions or the commit fail.
+    fn transaction<F>(&mut self, insertions: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        AnsiTransactionManager::begin_transaction(&mut self.conn)?;
+        let result = insertions(self)
+            .and_then(|()| Ok(AnsiTransactionManager::commit_transaction(&mut self.conn)?));
+        if result.is_err() {
+            self.cache.clear();
+            if let Err(e) = AnsiTransactionManager::rollback_transaction(&mut self.conn) {
+                warn!("Rollback error: {e}");
+            }
+        }
+        result
+    }
+
+    /// Gets the id of the last row inserted by the connection.
+    ///
+    /// # Errors
+    /// Will return [`Error`] if the query fails.
+    fn last_insert_id(&mut self) -> Result<u64, Error> {
+        Ok(diesel::select(last_insert_id()).get_result(&mut self.conn)?)
+    }
+}
+
+impl DatabaseAdapter for Connection {
+    type Error = Error;
+
+    fn new(db_url: &str, names: Names) -> Result<Self, Self::Error> {
+        trace!("Connection new function.");
+        info!("Connecting to the database.");
+        if !names.table_prefix.is_empty() {
+            return Err(Error::TablePrefix(names.table_prefix));
+        }
+        Ok(Self {
+            conn: MysqlConnection::establish(db_url)?,
+            cache: IdCaches::default(),
+            names,
+            run_id: None,
+            move_storage: MoveStorage::default(),
+        })
+    }
+
+    fn create_db(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_db function.");
+        info!("Creating the database.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-database.sql")))?;
+        self.conn.batch_execute(&self.names.sql("USE lichess;"))?;
+        Ok(self)
+    }
+
+    fn create_final_configuration(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_final_configuration function.");
+        info!("Creating FinalConfiguration table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-finalconf.sql")))?;
+        Ok(self)
+    }
+
+    fn create_game(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_game function.");
+        info!("Creating Game table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-game.sql")))?;
+        Ok(self)
+    }
+
+    fn create_move(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_move function.");
+        info!("Creating Move table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-move.sql")))?;
+        Ok(self)
+    }
+
+    fn create_opening(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_opening function.");
+        info!("Creating Opening table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-opening.sql")))?;
+        Ok(self)
+    }
+
+    fn create_player(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_player function.");
+        info!("Creating Player table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-player.sql")))?;
+        Ok(self)
+    }
+
+    fn create_ruleset(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_ruleset function.");
+        info!("Creating RuleSet table.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/create-ruleset.sql")))?;
+        Ok(self)
+    }
+
+    fn create_views(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_views function.");
+        info!("Creating views.");
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/view-descriptor.sql")))?;
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/view-finalboard.sql")))?;
+        self.conn
+            .batch_execute(&self.names.sql(include_str!("../sql/view-piecesleft.sql")))?;
+        Ok(self)
+    }
+
+    fn create_full_database(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_full_database function.");
+        info!("Creating full database.");
+        self.create_db()?
+            .create_final_configuration()?
+            .create_opening()?
+            .create_player()?
+            .create_ruleset()?
+            .create_game()?
+            .create_move()?
+            .create_views()?
+            .migrate()?;
+        info!("Database created correctly.");
+        Ok(self)
+    }
+
+    fn prewarm_cache(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection prewarm_cache function.");
+        info!("Loading the existing IDs into the cache.");
+        for (id, name) in player::table
+            .select((player::player_id, player::name))
+            .load::<(i32, String)>(&mut self.conn)?
+        {
+            self.cache.players.insert(name, u64::try_from(id)?);
+        }
+        for (id, name) in opening::table
+            .select((opening::opening_id, opening::name))
+            .load::<(i32, String)>(&mut self.conn)?
+        {
+            self.cache.openings.insert(name, u64::try_from(id)?);
+        }
+        for (id, name, url) in ruleset::table
+            .select((ruleset::ruleset_id, ruleset::name, ruleset::url_id))
+            .load::<(i32, String, String)>(&mut self.conn)?
+        {
+            self.cache.rulesets.insert((name, url), u64::try_from(id)?);
+        }
+        for (id, black_left, white_left, a, b, c, d, e, f, g, h) in final_configuration::table
+            .load::<(i32, u16, u16, u32, u32, u32, u32, u32, u32, u32, u32)>(&mut self.conn)?
+        {
+            self.cache.final_configurations.insert(
+                BoardConfiguration {
+                    black_left,
+                    white_left,
+                    rows: [a, b, c, d, e, f, g, h],
+                },
+                u64::try_from(id)?,
+            );
+        }
+        self.cache.log_sizes();
+        Ok(self)
+    }
+
+    fn defer_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection defer_constraints function.");
+        info!("Dropping the constraints of the Game and Move tables.");
+        for constraint in sql_query(
+            "SELECT TABLE_NAME, CONSTRAINT_NAME FROM information_schema.TABLE_CONSTRAINTS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME IN ('Game', 'Move') AND CONSTRAINT_TYPE = 'FOREIGN KEY'",
+        )
+        .load::<Constraint>(&mut self.conn)?
+        {
+            self.conn.batch_execute(&format!(
+                "ALTER TABLE `{}` DROP FOREIGN KEY `{}`;",
+                constraint.table, constraint.name
+            ))?;
+        }
+        for index in sql_query(
+            "SELECT DISTINCT TABLE_NAME, INDEX_NAME AS CONSTRAINT_NAME FROM information_schema.STATISTICS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME IN ('Game', 'Move') AND INDEX_NAME <> 'PRIMARY'",
+        )
+        .load::<Constraint>(&mut self.conn)?
+        {
+            self.conn.batch_execute(&format!(
+                "ALTER TABLE `{}` DROP INDEX `{}`;",
+                index.table, index.name
+            ))?;
+        }
+        self.conn
+            .batch_execute("ALTER TABLE Move DROP PRIMARY KEY;")?;
+        Ok(self)
+    }
+
+    fn constraint_violations(&mut self) -> Result<Vec<(String, u64)>, Self::Error> {
+        Ok(sql_query(include_str!("../sql/select-violations.sql"))
+            .load::<Violation>(&mut self.conn)?
+            .into_iter()
+            .map(|violation| (violation.name, violation.rows as u64))
+            .collect())
+    }
+
+    fn add_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection add_constraints function.");
+        self.conn
+            .batch_execute(include_str!("../sql/add-constraints.sql"))?;
+        Ok(self)
+    }
+
+    fn schema_version(&mut self) -> Result<u32, Self::Error> {
+        if !diesel::select(dsl::sql::<Bool>(
+            "EXISTS (SELECT 1 FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = 'SchemaVersion')",
+        ))
+        .get_result::<bool>(&mut self.conn)?
+        {
+            return Ok(0);
+        }
+        Ok(schema_version::table
+            .select(dsl::max(schema_version::version))
+            .first::<Option<u32>>(&mut self.conn)?
+            .unwrap_or_default())
+    }
+
+    fn apply_migration(&mut self, version: u32) -> Result<&mut Self, Self::Error> {
+        trace!("Connection apply_migration function.");
+        self.conn
+            .batch_execute(&self.names.sql(MYSQL_MIGRATIONS[version as usize - 1]))?;
+        insert_into(schema_version::table)
+            .values(schema_version::version.eq(version))
+            .execute(&mut self.conn)?;
+        Ok(self)
+    }
+
+    fn initialize_database(db_url: &str, names: Names, rebuild: bool) -> Result<Self, Self::Error> {
+        trace!("Connection initialize_database function.");
+        info!("Initializing connection.");
+        let mut connection = Self::new(db_url, names)?;
+        if rebuild {
+            info!("Rebuilding database.");
+        } else if connection
+            .conn
+            .batch_execute(&connection.names.sql("USE lichess;"))
+            .is_ok()
+        {
+            info!("Database already exists, proceeding.");
+            return Ok(connection);
+        } else {
+            info!("Database doesn't exist, creating.");
+        }
+        connection.create_full_database()?;
+        Ok(connection)
+    }
+
+    fn start_run(&mut self, run: &IngestionRun) -> Result<u64, Self::Error> {
+        trace!("Connection start_run function.");
+        insert_into(ingestion_run::table)
+            .values((
+                ingestion_run::version.eq(&run.version),
+                ingestion_run::features.eq(&run.features),
+                ingestion_run::arguments.eq(&run.arguments),
+                ingestion_run::sample_seed.eq(&run.sample_seed),
+                ingestion_run::sample_size.eq(run.sample_size.map(u32::try_from).transpose()?),
+                ingestion_run::source_file.eq(&run.source_file),
+            ))
+            .execute(&mut self.conn)?;
+        let run_id = self.last_insert_id()?;
+        self.run_id = Some(run_id);
+        Ok(run_id)
+    }
+
+    fn finish_run(&mut self, run: &IngestionRun) -> Result<&mut Self, Self::Error> {
+        trace!("Connection finish_run function.");
+        if let Some(run_id) = self.run_id {
+            diesel::update(ingestion_run::table.find(i32::try_from(run_id)?))
+                .set((
+                    ingestion_run::games_parsed.eq(u32::try_from(run.games_parsed)?),
+                    ingestion_run::games_inserted.eq(u32::try_from(run.games_inserted)?),
+                    ingestion_run::games_rejected.eq(u32::try_from(run.games_rejected)?),
+                    ingestion_run::games_with_errors.eq(u32::try_from(run.games_with_errors)?),
+                    ingestion_run::finished_at
+                        .eq(dsl::sql::<Nullable<SqlDatetime>>("CURRENT_TIMESTAMP")),
+                ))
+                .execute(&mut self.conn)?;
+        }
+        Ok(self)
+    }
+
+    fn source_runs(&mut self, source_file: &str) -> Result<Vec<u64>, Self::Error> {
+        Ok(ingestion_run::table
+            .select(ingestion_run::run_id)
+            .filter(ingestion_run::source_file.eq(source_file))
+            .filter(ingestion_run::purged_at.is_null())
+            .order(ingestion_run::run_id)
+            .load::<i32>(&mut self.conn)?
+            .into_iter()
+            .map(u64::try_from)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn purge_run(&mut self, run_id: u64) -> Result<u64, Self::Error> {
+        trace!("Connection purge_run function.");
+        let run_id = i32::try_from(run_id)?;
+        Ok(self.conn.transaction::<_, QueryError, _>(|conn| {
+            sql_query(include_str!("../sql/subtract-opening-stats.sql"))
+                .bind::<Integer, _>(run_id)
+                .execute(conn)?;
+            sql_query(include_str!("../sql/subtract-endgame-stats.sql"))
+                .bind::<Integer, _>(run_id)
+                .execute(conn)?;
+            conn.batch_execute(include_str!("../sql/delete-empty-stats.sql"))?;
+            diesel::delete(
+                r#move::table.filter(
+                    r#move::game_id.eq_any(
+                        game::table
+                            .select(game::game_id)
+                            .filter(game::run_id.eq(run_id)),
+                    ),
+                ),
+            )
+            .execute(conn)?;
+            let games =
+                diesel::delete(game::table.filter(game::run_id.eq(run_id))).execute(conn)?;
+            diesel::update(ingestion_run::table.find(run_id))
+                .set(
+                    ingestion_run::purged_at
+                        .eq(dsl::sql::<Nullable<SqlDatetime>>("CURRENT_TIMESTAMP")),
+                )
+                .execute(conn)?;
+            Ok(games as u64)
+        })?)
+    }
+
+    fn delete_orphans(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection delete_orphans function.");
+        self.conn
+            .batch_execute(include_str!("../sql/delete-orphans.sql"))?;
+        Ok(self)
+    }
+
+    fn select_table(&mut self, _query: &str) -> Result<Table, Self::Error> {
+        Err(Error::DynamicQuery)
+    }
+
+    fn insert_final_configuration(
+        &mut self,
+        configuration: &BoardConfiguration,
+    ) -> Result<u64, Self::Error> {
+        use final_configuration::dsl::{
+            a_row, b_row, c_row, d_row, e_row, end_black_pieces, end_white_pieces, f_row, fc_id,
+            g_row, h_row,
+        };
+        let [a, b, c, d, e, f, g, h] = configuration.rows;
+        let values = (
+            end_black_pieces.eq(configuration.black_left),
+            end_white_pieces.eq(configuration.white_left),
+            a_row.eq(a),
+            b_row.eq(b),
+            c_row.eq(c),
+            d_row.eq(d),
+            e_row.eq(e),
+            f_row.eq(f),
+            g_row.eq(g),
+            h_row.eq(h),
+        );
+        match insert_into(final_configuration::table)
+            .values(values)
+            .execute(&mut self.conn)
+        {
+            Ok(_) => return self.last_insert_id(),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(Error::Insertion(
+                    e,
+                    format!("FinalConfiguration: {configuration:?}"),
+                ));
+            }
+            Err(_) => (),
+        }
+        let id = final_configuration::table
+            .select(fc_id)
+            .filter(
+                end_black_pieces
+                    .eq(configuration.black_left)
+                    .and(end_white_pieces.eq(configuration.white_left))
+                    .and(a_row.eq(a))
+                    .and(b_row.eq(b))
+                    .and(c_row.eq(c))
+                    .and(d_row.eq(d))
+                    .and(e_row.eq(e))
+                    .and(f_row.eq(f))
+                    .and(g_row.eq(g))
+                    .and(h_row.eq(h)),
+            )
+            .first::<i32>(&mut self.conn)?;
+        Ok(u64::try_from(id)?)
+    }
+
+    fn insert_opening(&mut self, opening: &Opening, eco: Eco) -> Result<u64, Self::Error> {
+        match insert_into(opening::table)
+            .values((
+                opening::name.eq(&opening.0),
+                opening::eco_letter.eq(variant(eco.0)),
+                opening::eco_number.eq(eco.1.get()),
+            ))
+            .execute(&mut self.conn)
+        {
+            Ok(_) => return self.last_insert_id(),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(Error::Insertion(
+                    e,
+                    format!(
+                        "Opening: {} {}{:02}",
+                        opening.0,
+                        eco.0.as_char(),
+                        eco.1.get()
+                    ),
+                ));
+            }
+            Err(_) => (),
+        }
+        let id = opening::table
+            .select(opening::opening_id)
+            .filter(opening::name.eq(&opening.0))
+            .first::<i32>(&mut self.conn)?;
+        Ok(u64::try_from(id)?)
+    }
+
+    fn insert_player(&mut self, player: &Player) -> Result<u64, Self::Error> {
+        match insert_into(player::table)
+            .values(player::name.eq(&player.0))
+            .execute(&mut self.conn)
+        {
+            Ok(_) => return self.last_insert_id(),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(Error::Insertion(e, format!("Player: {}", player.0)));
+            }
+            Err(_) => (),
+        }
+        let id = player::table
+            .select(player::player_id)
+            .filter(player::name.eq(&player.0))
+            .first::<i32>(&mut self.conn)?;
+        Ok(u64::try_from(id)?)
+    }
+
+    fn insert_ruleset(&mut self, ruleset: &RuleSet) -> Result<u64, Self::Error> {
+        match insert_into(ruleset::table)
+            .values((
+                ruleset::name.eq(&ruleset.name),
+                ruleset::url_id.eq(&ruleset.url),
+                ruleset::kind.eq(variant(ruleset.kind)),
+            ))
+            .execute(&mut self.conn)
+        {
+            Ok(_) => return self.last_insert_id(),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(Error::Insertion(
+                    e,
+                    format!("RuleSet: {} - {}", ruleset.name, ruleset.kind.as_str()),
+                ));
+            }
+            Err(_) => (),
+        }
+        let id = ruleset::table
+            .select(ruleset::ruleset_id)
+            .filter(
+                ruleset::name
+                    .eq(&ruleset.name)
+                    .and(ruleset::url_id.eq(&ruleset.url)),
+            )
+            .first::<i32>(&mut self.conn)?;
+        Ok(u64::try_from(id)?)
+    }
+
+    fn insert_game(
+        &mut self,
+        game: &Game,
+        ruleset_id: u64,
+        opening_id: Option<u64>,
+        fc_id: u64,
+        white_id: Option<u64>,
+        black_id: Option<u64>,
+    ) -> Result<u64, Self::Error> {
+        let (start_time, increment) = game.time_control.0.unzip();
+        insert_into(game::table)
+            .values((
+                game::ruleset_id.eq(i32::try_from(ruleset_id)?),
+                game::opening_id.eq(opening_id.map(i32::try_from).transpose()?),
+                game::fc_id.eq(i32::try_from(fc_id)?),
+                game::white.eq(white_id.map(i32::try_from).transpose()?),
+                game::white_elo.eq(game.white_elo.0),
+                game::white_title.eq(game.white_title.map(variant)),
+                game::black.eq(black_id.map(i32::try_from).transpose()?),
+                game::black_elo.eq(game.black_elo.0),
+                game::black_title.eq(game.black_title.map(variant)),
+                game::start_time.eq(start_time),
+                game::increment.eq(increment),
+                game::result.eq(variant(game.result)),
+                game::termination.eq(variant(game.termination)),
+                game::datetime.eq(Datetime::new(game.utc_date.0, game.utc_time.0).0),
+                game::has_clock.eq(game.has_clock),
+                game::has_evaluations.eq(game.has_evaluations),
+                game::run_id.eq(self.run_id.map(i32::try_from).transpose()?),
+            ))
+            .execute(&mut self.conn)?;
+        self.last_insert_id()
+    }
+
+    fn insert_game_data(&mut self, game: &Game) -> Result<u64, Self::Error> {
+        let ruleset_id = self.ruleset_id(&game.ruleset)?;
+        let opening_id = if game.opening.0.is_empty() {
+            None
+        } else {
+            Some(self.opening_id(&game.opening, game.eco)?)
+        };
+        let fc_id = self.final_configuration_id(&game.final_conf)?;
+        let white_id = if game.white.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.white)?)
+        };
+        let black_id = if game.black.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.black)?)
+        };
+        self.insert_game(game, ruleset_id, opening_id, fc_id, white_id, black_id)
+    }
+
+    fn insert_move(&mut self, r#move: &Move, game_id: u64) -> Result<&mut Self, Self::Error> {
+        self.insert_moves(std::slice::from_ref(r#move), game_id)
+    }
+
+    fn insert_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error> {
+        if moves.is_empty() {
+            return Ok(self);
+        }
+        let game_id = i32::try_from(game_id)?;
+        let rows = moves
+            .iter()
+            .map(|r#move| {
+                let (eval_float, eval_int) = match r#move.eval {
+                    Some(Eval::Numeric(num)) => (Some(num), None),
+                    Some(Eval::Checkmate(checkmate)) => (None, Some(checkmate)),
+                    None => (None, None),
+                };
+                (
+                    r#move::game_id.eq(game_id),
+                    r#move::num.eq(r#move.num as u16),
+                    r#move::descriptor.eq(r#move.descriptor.to_u32()),
+                    r#move::eval_float.eq(eval_float),
+                    r#move::eval_int.eq(eval_int),
+                    r#move::clock.eq(r#move.clk.map(|clk| clk.0.0)),
+                )
+            })
+            .collect::<Vec<_>>();
+        insert_into(r#move::table)
+            .values(&rows)
+            .execute(&mut self.conn)?;
+        Ok(self)
+    }
+
+    fn insert_packed_moves(
+        &mut self,
+        moves: &[Move],
+        game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        diesel::update(game::table.find(i32::try_from(game_id)?))
+            .set(game::moves.eq(pack(moves)))
+            .execute(&mut self.conn)?;
+        Ok(self)
+    }
+
+    fn move_storage(&self) -> MoveStorage {
+        self.move_storage
+    }
+
+    fn update_rollups(
+        &mut self,
+        first_game_id: u64,
+        last_game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        let (first_game_id, last_game_id) =
+            (i64::try_from(first_game_id)?, i64::try_from(last_game_id)?);
+        sql_query(include_str!("../sql/add-opening-stats.sql"))
+            .bind::<BigInt, _>(first_game_id)
+            .bind::<BigInt, _>(last_game_id)
+            .execute(&mut self.conn)?;
+        sql_query(include_str!("../sql/add-endgame-stats.sql"))
+            .bind::<BigInt, _>(first_game_id)
+            .bind::<BigInt, _>(last_game_id)
+            .execute(&mut self.conn)?;
+        Ok(self)
+    }
+
+    fn insert_all(&mut self, data: &Data) -> Result<&mut Self, Self::Error> {
+        self.transaction(|connection| {
+            let game_id = connection.insert_game_data(&data.game)?;
+            connection
+                .store_moves(&data.moves, game_id)?
+                .update_rollups(game_id, game_id)?;
+            Ok(())
+        })?;
+        Ok(self)
+    }
+
+    fn insert_batch(&mut self, batch: &[(Game, Vec<Move>)]) -> Result<&mut Self, Self::Error> {
+        self.transaction(|connection| {
+            for (game, moves) in batch {
+                let game_id = connection.insert_game_data(game)?;
+                connection
+                    .store_moves(moves, game_id)?
+                    .update_rollups(game_id, game_id)?;
+            }
+            Ok(())
+        })?;
+        Ok(self)
+    }
+}
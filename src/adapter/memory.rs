@@ -0,0 +1,654 @@
+//! Module of an in-memory database, which keeps the rows of every table in vectors while enforcing the same constraints as the SQL schema, for tests and dry runs without a server.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+use log::{info, trace};
+
+use lichess::{
+    attributes::{BoardConfiguration, Eco, Opening, Player, RuleSet},
+    data::{Data, Game, Move, packed::pack},
+};
+
+use super::{DatabaseAdapter, IngestionRun, MoveStorage, Names, Table};
+
+/// An error of the in-memory database, which is a violation of one of the constraints of the SQL schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A row had the same key as another row of its table, along with the table and a description of the key.
+    Duplicate(&'static str, String),
+    /// A row referenced an ID that is not in the referenced table, along with the table and the ID.
+    ForeignKey(&'static str, u64),
+    /// An operation that the in-memory database doesn't support, since the ID of each row is its position in its table.
+    Unsupported(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate(table, key) => write!(f, "Duplicate entry {key} in the {table} table."),
+            Self::ForeignKey(table, id) => {
+                write!(f, "There is no row with ID {id} in the {table} table.")
+            }
+            Self::Unsupported(operation) => {
+                write!(f, "The in-memory database doesn't support {operation}.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A row of the Game table, with the IDs of the rows of the other tables it references.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct GameRow {
+    /// ID of the ruleset of the game.
+    pub ruleset_id: u64,
+    /// ID of the opening of the game, if it has one.
+    pub opening_id: Option<u64>,
+    /// ID of the final configuration of the game.
+    pub fc_id: u64,
+    /// ID of the player with the white pieces, if known.
+    pub white_id: Option<u64>,
+    /// ID of the player with the black pieces, if known.
+    pub black_id: Option<u64>,
+    /// ID of the run that inserted the game, if it was recorded.
+    pub run_id: Option<u64>,
+    /// The moves of the game packed into a blob, if they are stored packed.
+    pub packed_moves: Option<Vec<u8>>,
+    /// The game itself.
+    pub game: Game,
+}
+
+/// Number of rows of every table, to which they are truncated when rolling back a failed insertion.
+#[derive(Debug, Clone, Copy)]
+struct Savepoint {
+    /// Number of rows of the Player table.
+    players: usize,
+    /// Number of rows of the Opening table.
+    openings: usize,
+    /// Number of rows of the RuleSet table.
+    rulesets: usize,
+    /// Number of rows of the FinalConfiguration table.
+    final_configurations: usize,
+    /// Number of rows of the Game table.
+    games: usize,
+    /// Number of rows of the Move table.
+    moves: usize,
+}
+
+/// A database held in memory, where the ID of a row is its position in its table plus one, as an auto increment would assign it.
+#[derive(Debug, Default)]
+pub struct MemoryConnection {
+    /// Rows of the Player table.
+    pub players: Vec<Player>,
+    /// Rows of the Opening table, along with their ECO code.
+    pub openings: Vec<(Opening, Eco)>,
+    /// Rows of the RuleSet table.
+    pub rulesets: Vec<RuleSet>,
+    /// Rows of the FinalConfiguration table.
+    pub final_configurations: Vec<BoardConfiguration>,
+    /// Rows of the Game table.
+    pub games: Vec<GameRow>,
+    /// Rows of the Move table, along with the ID of their game.
+    pub moves: Vec<(u64, Move)>,
+    /// Rows of the IngestionRun table.
+    pub runs: Vec<IngestionRun>,
+    /// IDs of the players by their name, which is unique.
+    player_ids: HashMap<String, u64>,
+    /// IDs of the openings by their name, which is unique.
+    opening_ids: HashMap<String, u64>,
+    /// IDs of the rulesets by their name and url id, which are unique together.
+    ruleset_ids: HashMap<(String, String), u64>,
+    /// IDs of the final configurations, which are unique.
+    final_configuration_ids: HashMap<BoardConfiguration, u64>,
+    /// IDs of the games and numbers of the moves, which are the primary key of the Move table.
+    move_keys: HashSet<(u64, usize)>,
+    /// Version of the schema, as the last migration applied.
+    schema_version: u32,
+    /// ID of the run whose games are being inserted, if it is recorded.
+    run_id: Option<u64>,
+    /// Whether the foreign keys of the Game and Move tables and the primary key of Move are deferred, and thus not checked on insertion.
+    deferred: bool,
+    /// How the moves of the games are stored.
+    move_storage: MoveStorage,
+}
+
+/// Checks that an ID references one of the rows of a table.
+///
+/// # Errors
+/// Will return [`Error::ForeignKey`] if there is no row with that ID.
+fn check_reference(table: &'static str, id: u64, rows: usize) -> Result<(), Error> {
+    if id == 0 || id > rows as u64 {
+        return Err(Error::ForeignKey(table, id));
+    }
+    Ok(())
+}
+
+/// Counts the IDs that don't reference any of the rows of a table.
+fn orphans(ids: impl Iterator<Item = u64>, rows: usize) -> u64 {
+    ids.filter(|id| *id == 0 || *id > rows as u64).count() as u64
+}
+
+impl MemoryConnection {
+    /// Sets how the moves of the games inserted from now on are stored.
+    pub const fn set_move_storage(&mut self, move_storage: MoveStorage) {
+        self.move_storage = move_storage;
+    }
+
+    /// Marks the number of rows of every table, to roll back to them.
+    const fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            players: self.players.len(),
+            openings: self.openings.len(),
+            rulesets: self.rulesets.len(),
+            final_configurations: self.final_configurations.len(),
+            games: self.games.len(),
+            moves: self.moves.len(),
+        }
+    }
+
+    /// Removes every row inserted since the savepoint, as a transaction would when rolled back.
+    fn rollback(&mut self, savepoint: Savepoint) {
+        trace!("MemoryConnection rollback function.");
+        self.players.truncate(savepoint.players);
+        self.player_ids
+            .retain(|_, id| *id <= savepoint.players as u64);
+        self.openings.truncate(savepoint.openings);
+        self.opening_ids
+            .retain(|_, id| *id <= savepoint.openings as u64);
+        self.rulesets.truncate(savepoint.rulesets);
+        self.ruleset_ids
+            .retain(|_, id| *id <= savepoint.rulesets as u64);
+        self.final_configurations
+            .truncate(savepoint.final_configurations);
+        self.final_configuration_ids
+            .retain(|_, id| *id <= savepoint.final_configurations as u64);
+        self.games.truncate(savepoint.games);
+        for (game_id, r#move) in self.moves.drain(savepoint.moves..) {
+            self.move_keys.remove(&(game_id, r#move.num));
+        }
+    }
+
+    /// Writes the number of rows of every table, one table per line.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write_counts<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (table, rows) in [
+            ("Player", self.players.len()),
+            ("Opening", self.openings.len()),
+            ("RuleSet", self.rulesets.len()),
+            ("FinalConfiguration", self.final_configurations.len()),
+            ("Game", self.games.len()),
+            ("Move", self.moves.len()),
+        ] {
+            writeln!(writer, "{table}: {rows}")?;
+        }
+        Ok(())
+    }
+}
+
+impl DatabaseAdapter for MemoryConnection {
+    type Error = Error;
+
+    fn new(_db_url: &str, _names: Names) -> Result<Self, Self::Error> {
+        trace!("MemoryConnection new function.");
+        info!("Creating an in-memory database.");
+        Ok(Self::default())
+    }
+
+    fn create_db(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_db function.");
+        *self = Self::default();
+        Ok(self)
+    }
+
+    fn create_final_configuration(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_final_configuration function.");
+        self.final_configurations.clear();
+        self.final_configuration_ids.clear();
+        Ok(self)
+    }
+
+    fn create_game(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_game function.");
+        self.games.clear();
+        Ok(self)
+    }
+
+    fn create_move(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_move function.");
+        self.moves.clear();
+        self.move_keys.clear();
+        Ok(self)
+    }
+
+    fn create_opening(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_opening function.");
+        self.openings.clear();
+        self.opening_ids.clear();
+        Ok(self)
+    }
+
+    fn create_player(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_player function.");
+        self.players.clear();
+        self.player_ids.clear();
+        Ok(self)
+    }
+
+    fn create_ruleset(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_ruleset function.");
+        self.rulesets.clear();
+        self.ruleset_ids.clear();
+        Ok(self)
+    }
+
+    fn create_views(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_views function.");
+        Ok(self)
+    }
+
+    fn create_full_database(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection create_full_database function.");
+        self.create_db()?
+            .create_final_configuration()?
+            .create_opening()?
+            .create_player()?
+            .create_ruleset()?
+            .create_game()?
+            .create_move()?
+            .create_views()?
+            .migrate()
+    }
+
+    fn schema_version(&mut self) -> Result<u32, Self::Error> {
+        Ok(self.schema_version)
+    }
+
+    fn apply_migration(&mut self, version: u32) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection apply_migration function.");
+        self.schema_version = version;
+        Ok(self)
+    }
+
+    fn prewarm_cache(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection prewarm_cache function.");
+        Ok(self)
+    }
+
+    fn defer_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection defer_constraints function.");
+        self.deferred = true;
+        Ok(self)
+    }
+
+    fn constraint_violations(&mut self) -> Result<Vec<(String, u64)>, Self::Error> {
+        let mut keys = HashMap::new();
+        for (game_id, r#move) in &self.moves {
+            *keys.entry((*game_id, r#move.num)).or_insert(0) += 1;
+        }
+        let games = self.games.len();
+        Ok(vec![
+            (
+                String::from("Move primary key"),
+                keys.values().filter(|count| **count > 1).count() as u64,
+            ),
+            (
+                String::from("Move.GameId foreign key"),
+                orphans(self.moves.iter().map(|(id, _)| *id), games),
+            ),
+            (
+                String::from("Game.RuleSetId foreign key"),
+                orphans(
+                    self.games.iter().map(|game| game.ruleset_id),
+                    self.rulesets.len(),
+                ),
+            ),
+            (
+                String::from("Game.OpeningId foreign key"),
+                orphans(
+                    self.games.iter().filter_map(|game| game.opening_id),
+                    self.openings.len(),
+                ),
+            ),
+            (
+                String::from("Game.FCId foreign key"),
+                orphans(
+                    self.games.iter().map(|game| game.fc_id),
+                    self.final_configurations.len(),
+                ),
+            ),
+            (
+                String::from("Game.White foreign key"),
+                orphans(
+                    self.games.iter().filter_map(|game| game.white_id),
+                    self.players.len(),
+                ),
+            ),
+            (
+                String::from("Game.Black foreign key"),
+                orphans(
+                    self.games.iter().filter_map(|game| game.black_id),
+                    self.players.len(),
+                ),
+            ),
+            (
+                String::from("Game.RunId foreign key"),
+                orphans(
+                    self.games.iter().filter_map(|game| game.run_id),
+                    self.runs.len(),
+                ),
+            ),
+        ])
+    }
+
+    fn add_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection add_constraints function.");
+        self.deferred = false;
+        Ok(self)
+    }
+
+    fn initialize_database(
+        db_url: &str,
+        names: Names,
+        _rebuild: bool,
+    ) -> Result<Self, Self::Error> {
+        trace!("MemoryConnection initialize_database function.");
+        let mut connection = Self::new(db_url, names)?;
+        connection.create_full_database()?;
+        Ok(connection)
+    }
+
+    fn start_run(&mut self, run: &IngestionRun) -> Result<u64, Self::Error> {
+        trace!("MemoryConnection start_run function.");
+        self.runs.push(run.clone());
+        let run_id = self.runs.len() as u64;
+        self.run_id = Some(run_id);
+        Ok(run_id)
+    }
+
+    fn finish_run(&mut self, run: &IngestionRun) -> Result<&mut Self, Self::Error> {
+        trace!("MemoryConnection finish_run function.");
+        if let Some(run_id) = self.run_id {
+            self.runs[run_id as usize - 1] = run.clone();
+        }
+        Ok(self)
+    }
+
+    fn source_runs(&mut self, source_file: &str) -> Result<Vec<u64>, Self::Error> {
+        Ok(self
+            .runs
+            .iter()
+            .zip(1..)
+            .filter(|(run, _)| run.source_file.as_deref() == Some(source_file))
+            .map(|(_, id)| id)
+            .collect())
+    }
+
+    fn purge_run(&mut self, _run_id: u64) -> Result<u64, Self::Error> {
+        Err(Error::Unsupported("purging runs"))
+    }
+
+    fn delete_orphans(&mut self) -> Result<&mut Self, Self::Error> {
+        Err(Error::Unsupported("deleting rows"))
+    }
+
+    fn select_table(&mut self, _query: &str) -> Result<Table, Self::Error> {
+        Err(Error::Unsupported("SQL queries"))
+    }
+
+    fn insert_final_configuration(
+        &mut self,
+        final_configuration: &BoardConfiguration,
+    ) -> Result<u64, Self::Error> {
+        if let Some(id) = self.final_configuration_ids.get(final_configuration) {
+            return Ok(*id);
+        }
+        self.final_configurations.push(*final_configuration);
+        let id = self.final_configurations.len() as u64;
+        self.final_configuration_ids
+            .insert(*final_configuration, id);
+        Ok(id)
+    }
+
+    fn insert_opening(&mut self, opening: &Opening, eco: Eco) -> Result<u64, Self::Error> {
+        if let Some(id) = self.opening_ids.get(&opening.0) {
+            return Ok(*id);
+        }
+        self.openings.push((opening.clone(), eco));
+        let id = self.openings.len() as u64;
+        self.opening_ids.insert(opening.0.clone(), id);
+        Ok(id)
+    }
+
+    fn insert_player(&mut self, player: &Player) -> Result<u64, Self::Error> {
+        if let Some(id) = self.player_ids.get(&player.0) {
+            return Ok(*id);
+        }
+        self.players.push(player.clone());
+        let id = self.players.len() as u64;
+        self.player_ids.insert(player.0.clone(), id);
+        Ok(id)
+    }
+
+    fn insert_ruleset(&mut self, ruleset: &RuleSet) -> Result<u64, Self::Error> {
+        let key = (ruleset.name.clone(), ruleset.url.clone());
+        if let Some(id) = self.ruleset_ids.get(&key) {
+            return Ok(*id);
+        }
+        self.rulesets.push(ruleset.clone());
+        let id = self.rulesets.len() as u64;
+        self.ruleset_ids.insert(key, id);
+        Ok(id)
+    }
+
+    fn insert_game(
+        &mut self,
+        game: &Game,
+        ruleset_id: u64,
+        opening_id: Option<u64>,
+        fc_id: u64,
+        white_id: Option<u64>,
+        black_id: Option<u64>,
+    ) -> Result<u64, Self::Error> {
+        if !self.deferred {
+            check_reference("RuleSet", ruleset_id, self.rulesets.len())?;
+            if let Some(opening_id) = opening_id {
+                check_reference("Opening", opening_id, self.openings.len())?;
+            }
+            check_reference("FinalConfiguration", fc_id, self.final_configurations.len())?;
+            for player_id in [white_id, black_id].into_iter().flatten() {
+                check_reference("Player", player_id, self.players.len())?;
+            }
+            if let Some(run_id) = self.run_id {
+                check_reference("IngestionRun", run_id, self.runs.len())?;
+            }
+        }
+        self.games.push(GameRow {
+            ruleset_id,
+            opening_id,
+            fc_id,
+            white_id,
+            black_id,
+            run_id: self.run_id,
+            packed_moves: None,
+            game: game.clone(),
+        });
+        Ok(self.games.len() as u64)
+    }
+
+    fn insert_game_data(&mut self, game: &Game) -> Result<u64, Self::Error> {
+        let ruleset_id = self.insert_ruleset(&game.ruleset)?;
+        let opening_id = if game.opening.0.is_empty() {
+            None
+        } else {
+            Some(self.insert_opening(&game.opening, game.eco)?)
+        };
+        let fc_id = self.insert_final_configuration(&game.final_conf)?;
+        let white_id = if game.white.0.is_empty() {
+            None
+        } else {
+            Some(self.insert_player(&game.white)?)
+        };
+        let black_id = if game.black.0.is_empty() {
+            None
+        } else {
+            Some(self.insert_player(&game.black)?)
+        };
+        self.insert_game(game, ruleset_id, opening_id, fc_id, white_id, black_id)
+    }
+
+    fn insert_move(&mut self, r#move: &Move, game_id: u64) -> Result<&mut Self, Self::Error> {
+        self.insert_moves(std::slice::from_ref(r#move), game_id)
+    }
+
+    fn insert_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error> {
+        if !self.deferred {
+            check_reference("Game", game_id, self.games.len())?;
+        }
+        for r#move in moves {
+            if !self.move_keys.insert((game_id, r#move.num)) && !self.deferred {
+                return Err(Error::Duplicate(
+                    "Move",
+                    format!("{game_id}-{}", r#move.num),
+                ));
+            }
+            self.moves.push((game_id, r#move.clone()));
+        }
+        Ok(self)
+    }
+
+    fn insert_packed_moves(
+        &mut self,
+        moves: &[Move],
+        game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        if !self.deferred {
+            check_reference("Game", game_id, self.games.len())?;
+        }
+        if let Some(row) = (game_id as usize)
+            .checked_sub(1)
+            .and_then(|index| self.games.get_mut(index))
+        {
+            row.packed_moves = Some(pack(moves));
+        }
+        Ok(self)
+    }
+
+    fn move_storage(&self) -> MoveStorage {
+        self.move_storage
+    }
+
+    fn update_rollups(
+        &mut self,
+        _first_game_id: u64,
+        _last_game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn insert_all(&mut self, data: &Data) -> Result<&mut Self, Self::Error> {
+        self.insert_batch(&[(data.game.clone(), data.moves.clone())])
+    }
+
+    fn insert_batch(&mut self, batch: &[(Game, Vec<Move>)]) -> Result<&mut Self, Self::Error> {
+        let savepoint = self.savepoint();
+        let result = batch.iter().try_for_each(|(game, moves)| {
+            self.insert_game_data(game)
+                .and_then(|game_id| self.store_moves(moves, game_id).map(|_| ()))
+        });
+        if let Err(e) = result {
+            self.rollback(savepoint);
+            return Err(e);
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lichess::{
+        attributes::{Opening, Player},
+        data::{Data, packed::unpack},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{DatabaseAdapter, Error, MemoryConnection, MoveStorage, Names};
+
+    /// Tests that the values shared between games are inserted once, that a game whose moves break the primary key of the Move table is rolled back completely, and that packed moves skip the Move table.
+    #[test]
+    fn memory_insertion_test() {
+        let mut connection =
+            MemoryConnection::initialize_database("", Names::default(), false).unwrap();
+
+        let player = Player(String::from("DrNykterstein"));
+        let id = connection.insert_player(&player).unwrap();
+        assert_eq!(connection.insert_player(&player).unwrap(), id);
+
+        let mut data = Data::default();
+        data.game.white = player;
+        data.r#move.num = 1;
+        data.moves.push(data.r#move.clone());
+        connection.insert_all(&data).unwrap();
+        connection.insert_all(&data).unwrap();
+        assert_eq!(connection.games.len(), 2);
+        assert_eq!(connection.games[1].white_id, Some(id));
+        assert_eq!(connection.players.len(), 1);
+        assert_eq!(connection.final_configurations.len(), 1);
+        assert_eq!(connection.moves.len(), 2);
+
+        data.game.opening = Opening(String::from("Sicilian Defense"));
+        data.moves.push(data.r#move.clone());
+        assert_eq!(
+            connection.insert_all(&data).unwrap_err(),
+            Error::Duplicate("Move", String::from("3-1"))
+        );
+        assert_eq!(connection.games.len(), 2);
+        assert_eq!(connection.openings.len(), 0);
+        assert_eq!(connection.moves.len(), 2);
+        assert_eq!(
+            connection.insert_moves(&data.moves, 3).unwrap_err(),
+            Error::ForeignKey("Game", 3)
+        );
+
+        connection.set_move_storage(MoveStorage::Packed);
+        connection.insert_all(&data).unwrap();
+        assert_eq!(connection.moves.len(), 2);
+        let packed_moves = connection.games[2].packed_moves.as_deref().unwrap();
+        assert_eq!(unpack(packed_moves).unwrap().len(), 2);
+    }
+
+    /// Tests that the deferred constraints are only added back if none of them is violated.
+    #[test]
+    fn deferred_constraints_test() {
+        let mut connection =
+            MemoryConnection::initialize_database("", Names::default(), false).unwrap();
+        connection.defer_constraints().unwrap();
+
+        let mut data = Data::default();
+        data.moves.push(data.r#move.clone());
+        connection.insert_all(&data).unwrap();
+        assert_eq!(connection.restore_constraints().unwrap(), Vec::new());
+        assert_eq!(
+            connection.insert_moves(&data.moves, 2).unwrap_err(),
+            Error::ForeignKey("Game", 2)
+        );
+
+        connection.defer_constraints().unwrap();
+        connection.insert_moves(&data.moves, 1).unwrap();
+        connection.insert_moves(&data.moves, 2).unwrap();
+        assert_eq!(
+            connection.restore_constraints().unwrap(),
+            vec![
+                (String::from("Move primary key"), 1),
+                (String::from("Move.GameId foreign key"), 1)
+            ]
+        );
+    }
+}
@@ -1,48 +1,278 @@
 //! Module that handles all database operations, from connecting to it, creating the database and its tables and inserting the data.
 
-#[cfg(all(
-    any(feature = "time-mysql", feature = "chrono-mysql"),
-    any(feature = "time-diesel", feature = "chrono-diesel")
+#[cfg(any(
+    all(
+        any(feature = "time-mysql", feature = "chrono-mysql"),
+        any(
+            feature = "time-diesel",
+            feature = "chrono-diesel",
+            feature = "time-postgres",
+            feature = "chrono-postgres",
+            feature = "time-sqlite",
+            feature = "chrono-sqlite"
+        )
+    ),
+    all(
+        any(feature = "time-diesel", feature = "chrono-diesel"),
+        any(
+            feature = "time-postgres",
+            feature = "chrono-postgres",
+            feature = "time-sqlite",
+            feature = "chrono-sqlite"
+        )
+    ),
+    all(
+        any(feature = "time-postgres", feature = "chrono-postgres"),
+        any(feature = "time-sqlite", feature = "chrono-sqlite")
+    )
 ))]
-compile_error!("The features of mysql and of diesel cannot be enabled at the same time.");
+compile_error!(
+    "Only one of the features of mysql, diesel, postgres or sqlite can be enabled at the same time. Build with --no-default-features to leave the default time-mysql out."
+);
 
 #[cfg(not(any(
     feature = "time-mysql",
     feature = "chrono-mysql",
     feature = "time-diesel",
-    feature = "chrono-diesel"
+    feature = "chrono-diesel",
+    feature = "time-postgres",
+    feature = "chrono-postgres",
+    feature = "time-sqlite",
+    feature = "chrono-sqlite"
 )))]
-compile_error!("At least one of the features of mysql or of diesel must be enabled.");
+compile_error!(
+    "At least one of the features of mysql, diesel, postgres or sqlite must be enabled."
+);
+
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+use log::{info, trace};
 
 use lichess::{
     attributes::{BoardConfiguration, Eco, Opening, Player, RuleSet},
     data::{Data, Game, Move},
 };
 
+mod cache;
+pub use cache::DEFAULT_CACHE_SIZE;
+use cache::IdCaches;
+
+mod names;
+pub use names::{DEFAULT_DATABASE_NAME, Names};
+
+pub mod memory;
+
+mod run;
+pub use run::IngestionRun;
+
+mod table;
+pub use table::Table;
+
 #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
 mod mysql;
 
+#[cfg(feature = "csv")]
+pub mod csv;
+
+#[cfg(feature = "parquet")]
+pub mod parquet;
+
 #[cfg(any(feature = "time-diesel", feature = "chrono-diesel"))]
 mod diesel;
+#[cfg(any(feature = "time-diesel", feature = "chrono-diesel"))]
+pub mod schema;
+
+#[cfg(any(feature = "time-postgres", feature = "chrono-postgres"))]
+mod postgres;
 
-/// An adapter for the MySQL database, holding a connection.
-#[derive(Debug)]
+#[cfg(any(feature = "time-sqlite", feature = "chrono-sqlite"))]
+mod sqlite;
+
+/// Version of the schema of the database this program inserts into, which is the number of migrations there are.
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Migrations of the MySQL schema, the one at index `i` upgrading it from version `i` to `i + 1`, shared by the mysql and Diesel adapters since both insert into the same schema.
+#[cfg(any(
+    feature = "time-mysql",
+    feature = "chrono-mysql",
+    feature = "time-diesel",
+    feature = "chrono-diesel"
+))]
+const MYSQL_MIGRATIONS: [&str; SCHEMA_VERSION as usize] = [
+    include_str!("../sql/migrations/001-schema-version.sql"),
+    include_str!("../sql/migrations/002-ingestion-run.sql"),
+    include_str!("../sql/migrations/003-run-source.sql"),
+    include_str!("../sql/migrations/004-packed-moves.sql"),
+    include_str!("../sql/migrations/005-rollups.sql"),
+];
+
+/// How the moves of the games are stored in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MoveStorage {
+    /// One row of the Move table per move.
+    #[default]
+    Rows,
+    /// One blob per game in the Moves column of the Game table, laid out as documented in [`packed`](lichess::data::packed). The views over the Move table don't include these games.
+    Packed,
+}
+
+/// An adapter for the database, holding a connection.
+#[cfg_attr(
+    any(
+        feature = "time-mysql",
+        feature = "chrono-mysql",
+        feature = "time-sqlite",
+        feature = "chrono-sqlite"
+    ),
+    derive(Debug)
+)]
 pub struct Connection {
     /// The [`mysql`] [`Conn`](::mysql::Conn) to interact with the MySQL database.
     #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
     conn: ::mysql::Conn,
+    /// The [`Opts`](::mysql::Opts) the MySQL connection was opened with, to open it again if it is lost.
+    #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
+    opts: ::mysql::Opts,
+    /// The [`diesel`] [`MysqlConnection`](::diesel::MysqlConnection) to interact with the MySQL database.
+    #[cfg(any(feature = "time-diesel", feature = "chrono-diesel"))]
+    conn: ::diesel::MysqlConnection,
+    /// The [`postgres`] [`Client`](::postgres::Client) to interact with the PostgreSQL database.
+    #[cfg(any(feature = "time-postgres", feature = "chrono-postgres"))]
+    conn: ::postgres::Client,
+    /// The [`rusqlite`] [`Connection`](::rusqlite::Connection) to interact with the SQLite database.
+    #[cfg(any(feature = "time-sqlite", feature = "chrono-sqlite"))]
+    conn: ::rusqlite::Connection,
+    /// The caches of the IDs of the values shared between games.
+    cache: IdCaches,
+    /// The names of the database and its tables.
+    names: Names,
+    /// ID of the run whose games are being inserted, if it is recorded.
+    run_id: Option<u64>,
+    /// How the moves of the games are stored.
+    move_storage: MoveStorage,
 }
 
+impl Connection {
+    /// Replaces the caches of IDs with ones that keep at most `capacity` IDs each, disabling them if it is 0.
+    pub fn set_cache_size(&mut self, capacity: usize) {
+        trace!("Connection set_cache_size function.");
+        self.cache = IdCaches::new(capacity);
+    }
+
+    /// Sets how the moves of the games inserted from now on are stored.
+    pub const fn set_move_storage(&mut self, move_storage: MoveStorage) {
+        self.move_storage = move_storage;
+    }
+
+    /// Checks that the schema of the database has the version this program inserts into.
+    ///
+    /// # Errors
+    /// Will return a [`Box`]ed [`Error`](std::error::Error) if the query fails or the schema has another version, in which case either the database must be migrated or this program updated.
+    pub fn check_schema_version(&mut self) -> Result<&mut Self, Box<dyn std::error::Error>> {
+        trace!("Connection check_schema_version function.");
+        let version = self.schema_version()?;
+        match version.cmp(&SCHEMA_VERSION) {
+            Ordering::Less => Err(format!("The schema of the database has version {version}, older than the version {SCHEMA_VERSION} this program inserts into. Please upgrade it with the \"migrate\" subcommand.").into()),
+            Ordering::Greater => Err(format!("The schema of the database has version {version}, newer than the version {SCHEMA_VERSION} this program inserts into. Please update this program.").into()),
+            Ordering::Equal => Ok(self),
+        }
+    }
+
+    /// Logs the hit rates of the caches of IDs.
+    pub fn log_cache_hit_rates(&self) {
+        self.cache.log_hit_rates();
+    }
+
+    /// Gets the ID of a [`RuleSet`] from the cache, inserting it into the RuleSet table if it is not cached.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn ruleset_id(&mut self, ruleset: &RuleSet) -> Result<u64, <Self as DatabaseAdapter>::Error> {
+        let key = (ruleset.name.clone(), ruleset.url.clone());
+        if let Some(id) = self.cache.rulesets.get(&key) {
+            return Ok(id);
+        }
+        let id = self.insert_ruleset(ruleset)?;
+        self.cache.rulesets.insert(key, id);
+        Ok(id)
+    }
+
+    /// Gets the ID of an [`Opening`] from the cache, inserting it into the Opening table if it is not cached.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn opening_id(
+        &mut self,
+        opening: &Opening,
+        eco: Eco,
+    ) -> Result<u64, <Self as DatabaseAdapter>::Error> {
+        if let Some(id) = self.cache.openings.get(&opening.0) {
+            return Ok(id);
+        }
+        let id = self.insert_opening(opening, eco)?;
+        self.cache.openings.insert(opening.0.clone(), id);
+        Ok(id)
+    }
+
+    /// Gets the ID of a [`BoardConfiguration`] from the cache, inserting it into the FinalConfiguration table if it is not cached.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn final_configuration_id(
+        &mut self,
+        final_configuration: &BoardConfiguration,
+    ) -> Result<u64, <Self as DatabaseAdapter>::Error> {
+        if let Some(id) = self.cache.final_configurations.get(final_configuration) {
+            return Ok(id);
+        }
+        let id = self.insert_final_configuration(final_configuration)?;
+        self.cache
+            .final_configurations
+            .insert(*final_configuration, id);
+        Ok(id)
+    }
+
+    /// Gets the ID of a [`Player`] from the cache, inserting it into the Player table if it is not cached.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn player_id(&mut self, player: &Player) -> Result<u64, <Self as DatabaseAdapter>::Error> {
+        if let Some(id) = self.cache.players.get(&player.0) {
+            return Ok(id);
+        }
+        let id = self.insert_player(player)?;
+        self.cache.players.insert(player.0.clone(), id);
+        Ok(id)
+    }
+}
+
+/// Formats the variant of an enum attribute as the value of its database enumeration, since their names coincide.
+#[cfg(any(
+    feature = "csv",
+    feature = "parquet",
+    feature = "time-diesel",
+    feature = "chrono-diesel",
+    feature = "time-postgres",
+    feature = "chrono-postgres",
+    feature = "time-sqlite",
+    feature = "chrono-sqlite"
+))]
+fn variant<T: std::fmt::Debug>(value: T) -> String {
+    format!("{value:?}")
+}
+
+/// Connection to the database the games are inserted into, implemented by the MySQL, Diesel, PostgreSQL and SQLite adapters, of which one is compiled in, and by the in-memory one of the dry runs.
 pub trait DatabaseAdapter: Sized {
-    type Error: std::error::Error;
+    type Error: std::error::Error + 'static;
 
-    /// Constructs a connection to MySQL.
+    /// Constructs a connection to the database, which uses the given names for the database and its tables.
     ///
     /// # Errors
     /// Will return [`DatabaseAdapter::Error`] if the connection fails to start.
-    fn new(db_url: &str) -> Result<Self, Self::Error>;
+    fn new(db_url: &str, names: Names) -> Result<Self, Self::Error>;
 
-    /// Creates and selects the lichess database.
+    /// Creates and selects the database.
     ///
     /// # Errors
     /// Will return [`DatabaseAdapter::Error`] if the creation or selection fails.
@@ -90,17 +320,125 @@ pub trait DatabaseAdapter: Sized {
     /// Will return [`DatabaseAdapter::Error`] if any of the creations fails.
     fn create_views(&mut self) -> Result<&mut Self, Self::Error>;
 
-    /// Creates the lichess database, selects it and creates all its tables.
+    /// Creates the database, selects it, creates all its tables and migrates it to the current version.
     ///
     /// # Errors
     /// Will return [`DatabaseAdapter::Error`] if the creation or selection fails.
     fn create_full_database(&mut self) -> Result<&mut Self, Self::Error>;
 
-    /// Gets a connection to MySQL, creates the lichess database and all its tables and selects it. It only rebuilds it if the database didn't already exist or rebuild is set to true.
+    /// Gets the version of the schema of the database, which is 0 if it predates the versioning.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the query fails.
+    fn schema_version(&mut self) -> Result<u32, Self::Error>;
+
+    /// Applies the migration that upgrades the schema to the given version from the previous one and records it in the SchemaVersion table.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the migration or the recording fails.
+    fn apply_migration(&mut self, version: u32) -> Result<&mut Self, Self::Error>;
+
+    /// Upgrades the schema of the database to [`SCHEMA_VERSION`], applying the pending migrations in order, so that the data already in it is kept.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the migrations fail.
+    fn migrate(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection migrate function.");
+        let current = self.schema_version()?;
+        if current >= SCHEMA_VERSION {
+            info!("The schema is already at version {current}.");
+        }
+        for version in current + 1..=SCHEMA_VERSION {
+            info!("Migrating the schema to version {version}.");
+            self.apply_migration(version)?;
+        }
+        Ok(self)
+    }
+
+    /// Loads the IDs of the players, openings, rulesets and final configurations already in the database into the caches, so that appending to an existing database doesn't query them again.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the selections fail.
+    fn prewarm_cache(&mut self) -> Result<&mut Self, Self::Error>;
+
+    /// Drops the foreign keys and secondary indexes of the Game and Move tables, along with the primary key of Move, so that a bulk load doesn't check them on every row.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the drops fail.
+    fn defer_constraints(&mut self) -> Result<&mut Self, Self::Error>;
+
+    /// Counts the rows that violate each of the constraints dropped by [`DatabaseAdapter::defer_constraints`], along with the name of the constraint.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the query fails.
+    fn constraint_violations(&mut self) -> Result<Vec<(String, u64)>, Self::Error>;
+
+    /// Adds back the constraints dropped by [`DatabaseAdapter::defer_constraints`], building their indexes.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the constraints can't be added.
+    fn add_constraints(&mut self) -> Result<&mut Self, Self::Error>;
+
+    /// Validates the constraints dropped by [`DatabaseAdapter::defer_constraints`] and adds them back if none of them is violated, returning the violated ones along with their number of violations otherwise.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the validation or the addition fail.
+    fn restore_constraints(&mut self) -> Result<Vec<(String, u64)>, Self::Error> {
+        trace!("Connection restore_constraints function.");
+        info!("Validating the deferred constraints.");
+        let violations: Vec<_> = self
+            .constraint_violations()?
+            .into_iter()
+            .filter(|(_, rows)| *rows > 0)
+            .collect();
+        if violations.is_empty() {
+            info!("Adding back the deferred constraints.");
+            self.add_constraints()?;
+        }
+        Ok(violations)
+    }
+
+    /// Gets a connection to the database, creates the database with the given names and all its tables and selects it. It only rebuilds it if the database didn't already exist or rebuild is set to true.
     ///
     /// # Errors
     /// Will return [`DatabaseAdapter::Error`] if the connection fails to start or the creation or selection fails.
-    fn initialize_database(db_url: &str, rebuild: bool) -> Result<Self, Self::Error>;
+    fn initialize_database(db_url: &str, names: Names, rebuild: bool) -> Result<Self, Self::Error>;
+
+    /// Inserts the record of a run that starts now into the IngestionRun table, so that every game inserted afterwards references it.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn start_run(&mut self, run: &IngestionRun) -> Result<u64, Self::Error>;
+
+    /// Updates the record of the current run in the IngestionRun table with its counts of games and the time it finished.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the update fails.
+    fn finish_run(&mut self, run: &IngestionRun) -> Result<&mut Self, Self::Error>;
+
+    /// Gets the IDs of the runs that inserted the games of a source file and haven't been purged.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the selection fails.
+    fn source_runs(&mut self, source_file: &str) -> Result<Vec<u64>, Self::Error>;
+
+    /// Deletes the games of a run along with their moves in one transaction, subtracting them from the rollup tables and marking the run as purged, and returns the number of games deleted.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the deletions fail, in which case none of them is kept.
+    fn purge_run(&mut self, run_id: u64) -> Result<u64, Self::Error>;
+
+    /// Deletes the players, openings, rulesets and final configurations that no game references.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the deletions fail.
+    fn delete_orphans(&mut self) -> Result<&mut Self, Self::Error>;
+
+    /// Runs a query that selects rows, getting the names of its columns and the values of its rows as text.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the query fails.
+    fn select_table(&mut self, query: &str) -> Result<Table, Self::Error>;
 
     /// Inserts a [`BoardConfiguration`] into the FinalConfiguration table.
     ///
@@ -162,9 +500,56 @@ pub trait DatabaseAdapter: Sized {
     /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
     fn insert_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error>;
 
+    /// Packs a [`Vec`] of [`Move`]s into the Moves column of their game in the Game table.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the update fails.
+    fn insert_packed_moves(
+        &mut self,
+        moves: &[Move],
+        game_id: u64,
+    ) -> Result<&mut Self, Self::Error>;
+
+    /// Gets how the moves of the games are stored.
+    fn move_storage(&self) -> MoveStorage;
+
+    /// Stores a [`Vec`] of [`Move`]s either into the Move table or packed into their game, depending on the [`MoveStorage`].
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    fn store_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error> {
+        match self.move_storage() {
+            MoveStorage::Rows => self.insert_moves(moves, game_id),
+            MoveStorage::Packed => self.insert_packed_moves(moves, game_id),
+        }
+    }
+
+    /// Adds the games whose IDs are in the range, both included, to the rollup tables: OpeningStats, which counts the games and results per month, opening family, speed and bucket of 100 points of average Elo, and EndgameStats, which counts them per final material, bucket of average Elo and result. It must run in the transaction that inserted the games, so that each game is counted exactly once however the runs are resumed.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the upserts fail.
+    fn update_rollups(
+        &mut self,
+        first_game_id: u64,
+        last_game_id: u64,
+    ) -> Result<&mut Self, Self::Error>;
+
     /// Inserts a [`Data`]'s [`Game`] and [`Vec`] of [`Move`]s into the Game, RuleSet, Opening, FinalConfiguration, Player and Move tables.
     ///
     /// # Errors
     /// Will return [`DatabaseAdapter::Error`] if any of the insertions fail.
     fn insert_all(&mut self, data: &Data) -> Result<&mut Self, Self::Error>;
+
+    /// Inserts a batch of [`Game`]s, each along with its [`Vec`] of [`Move`]s, into the Game, RuleSet, Opening, FinalConfiguration, Player and Move tables. By default they are inserted one after the other.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the insertions fail.
+    fn insert_batch(&mut self, batch: &[(Game, Vec<Move>)]) -> Result<&mut Self, Self::Error> {
+        for (game, moves) in batch {
+            let game_id = self.insert_game_data(game)?;
+            self.store_moves(moves, game_id)?;
+            self.update_rollups(game_id, game_id)?;
+        }
+        Ok(self)
+    }
 }
@@ -1,22 +1,166 @@
-use log::{info, trace};
+use std::{thread::sleep, time::Duration};
 
-use mysql::{Conn, prelude::Queryable};
+use log::{info, trace, warn};
+
+use mysql::{Conn, DriverError, Opts, Params, Value, params, prelude::Queryable};
+
+#[cfg(feature = "csv")]
+use mysql::LocalInfileHandler;
+#[cfg(feature = "csv")]
+use std::{fs::File, io, path::Path, str::from_utf8};
 
 use lichess::{
     attributes::{BoardConfiguration, Eco, Opening, Player, RuleSet},
-    data::{Data, Game, Move},
+    data::{Data, Game, Move, packed::pack},
+};
+
+use super::{
+    Connection, DatabaseAdapter, IdCaches, IngestionRun, MYSQL_MIGRATIONS, MoveStorage, Names,
+    Table,
 };
 
-use super::{Connection, DatabaseAdapter};
+/// MySQL error code of an insertion of a duplicate entry of a unique key.
+const ER_DUP_ENTRY: u16 = 1062;
+/// MySQL error codes after which a statement may succeed if it is tried again: a lock wait timeout and a deadlock.
+const TRANSIENT_CODES: [u16; 2] = [1205, 1213];
+/// MySQL error codes after which the connection must be opened again: too many connections, the server shutting down, the server gone away and the connection lost.
+const CONNECTION_CODES: [u16; 4] = [1040, 1053, 2006, 2013];
+/// Number of times a failed transaction is tried again if its error is transient.
+const RETRIES: u32 = 5;
+/// Time waited before trying a failed transaction again for the first time, which doubles with every retry.
+const BACKOFF: Duration = Duration::from_millis(200);
+
+/// The way an error of MySQL is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+    /// A duplicate entry of a unique key, after which the existing ID is selected.
+    Duplicate,
+    /// A failure that may not happen again, after which the transaction is tried again.
+    Transient,
+    /// A lost connection, after which the connection is opened again and the transaction tried again.
+    Connection,
+    /// Any other error, which is returned.
+    Permanent,
+}
+
+/// Classifies an error of MySQL by its error code, or by its kind if it didn't come from the server.
+fn error_kind(e: &mysql::Error) -> ErrorKind {
+    match e {
+        mysql::Error::MySqlError(mse) if mse.code == ER_DUP_ENTRY => ErrorKind::Duplicate,
+        mysql::Error::MySqlError(mse) if TRANSIENT_CODES.contains(&mse.code) => {
+            ErrorKind::Transient
+        }
+        mysql::Error::MySqlError(mse) if CONNECTION_CODES.contains(&mse.code) => {
+            ErrorKind::Connection
+        }
+        mysql::Error::IoError(_)
+        | mysql::Error::CodecError(_)
+        | mysql::Error::DriverError(
+            DriverError::ConnectTimeout
+            | DriverError::CouldNotConnect(_)
+            | DriverError::PacketOutOfSync
+            | DriverError::Timeout,
+        ) => ErrorKind::Connection,
+        _ => ErrorKind::Permanent,
+    }
+}
+
+/// Formats a value selected by a query as text, or [`None`] if it is NULL.
+fn value_text(value: Value) -> Option<String> {
+    match value {
+        Value::NULL => None,
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::Int(int) => Some(int.to_string()),
+        Value::UInt(uint) => Some(uint.to_string()),
+        Value::Float(float) => Some(float.to_string()),
+        Value::Double(double) => Some(double.to_string()),
+        Value::Date(year, month, day, hour, minute, second, _) => Some(format!(
+            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}"
+        )),
+        Value::Time(negative, days, hours, minutes, seconds, _) => Some(format!(
+            "{}{:02}:{minutes:02}:{seconds:02}",
+            if negative { "-" } else { "" },
+            days * 24 + u32::from(hours)
+        )),
+    }
+}
+
+/// Maximum number of games of each multi-row insertion, so that its placeholders stay under MySQL's limit of 65535.
+const GAME_ROWS: usize = 2048;
+/// Maximum number of moves of each multi-row insertion, so that its placeholders stay under MySQL's limit of 65535.
+const MOVE_ROWS: usize = 8192;
+/// Names of the parameters of a game insertion, in the order of the columns of the multi-row insertion.
+const GAME_PARAMS: [&str; 17] = [
+    "ruleset_id",
+    "opening_id",
+    "fc_id",
+    "white",
+    "white_elo",
+    "white_title",
+    "black",
+    "black_elo",
+    "black_title",
+    "start_time",
+    "increment",
+    "result",
+    "termination",
+    "datetime",
+    "has_clock",
+    "has_evaluations",
+    "run_id",
+];
+/// Names of the parameters of a move insertion, in the order of the columns of the multi-row insertion.
+const MOVE_PARAMS: [&str; 6] = [
+    "game_id",
+    "num",
+    "descriptor",
+    "eval_float",
+    "eval_int",
+    "clock",
+];
+
+/// Appends the values of named parameters to a list of positional ones, following the order of the names.
+fn push_positional(values: &mut Vec<Value>, params: Params, names: &[&str]) {
+    if let Params::Named(mut params) = params {
+        values.extend(
+            names
+                .iter()
+                .map(|name| params.remove(name.as_bytes()).unwrap_or(Value::NULL)),
+        );
+    }
+}
+
+/// Completes the beginning of a multi-row insertion query with a row of placeholders for each of the rows.
+fn multi_row_query(query: &str, columns: usize, rows: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(", "));
+    format!("{query}{};", vec![row; rows].join(",\n"))
+}
+
+/// Appends a description of the value that was being inserted to the message of a MySQL error.
+fn with_context(e: mysql::Error, context: String) -> mysql::Error {
+    match e {
+        mysql::Error::MySqlError(mut mse) => {
+            mse.message.push_str(&format!(" ({context})"));
+            mysql::Error::MySqlError(mse)
+        }
+        e => e,
+    }
+}
 
 impl DatabaseAdapter for Connection {
     type Error = mysql::Error;
 
-    fn new(db_url: &str) -> Result<Self, Self::Error> {
+    fn new(db_url: &str, names: Names) -> Result<Self, Self::Error> {
         trace!("Connection new function.");
         info!("Connecting to the database.");
+        let opts = Opts::from_url(db_url)?;
         Ok(Self {
-            conn: Conn::new(db_url)?,
+            conn: Conn::new(opts.clone())?,
+            opts,
+            cache: IdCaches::default(),
+            names,
+            run_id: None,
+            move_storage: MoveStorage::default(),
         })
     }
 
@@ -24,8 +168,8 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_db function.");
         info!("Creating the database.");
         self.conn
-            .query_drop(include_str!("../sql/create-database.sql"))?;
-        self.conn.select_db("lichess")?;
+            .query_drop(self.names.sql(include_str!("../sql/create-database.sql")))?;
+        self.conn.select_db(&self.names.database)?;
         Ok(self)
     }
 
@@ -33,7 +177,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_final_configuration function.");
         info!("Creating FinalConfiguration table.");
         self.conn
-            .query_drop(include_str!("../sql/create-finalconf.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-finalconf.sql")))?;
         Ok(self)
     }
 
@@ -41,7 +185,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_game function.");
         info!("Creating Game table.");
         self.conn
-            .query_drop(include_str!("../sql/create-game.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-game.sql")))?;
         Ok(self)
     }
 
@@ -49,7 +193,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_move function.");
         info!("Creating Move table.");
         self.conn
-            .query_drop(include_str!("../sql/create-move.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-move.sql")))?;
         Ok(self)
     }
 
@@ -57,7 +201,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_opening function.");
         info!("Creating Opening table.");
         self.conn
-            .query_drop(include_str!("../sql/create-opening.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-opening.sql")))?;
         Ok(self)
     }
 
@@ -65,7 +209,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_player function.");
         info!("Creating Player table.");
         self.conn
-            .query_drop(include_str!("../sql/create-player.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-player.sql")))?;
         Ok(self)
     }
 
@@ -73,7 +217,7 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_ruleset function.");
         info!("Creating RuleSet table.");
         self.conn
-            .query_drop(include_str!("../sql/create-ruleset.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/create-ruleset.sql")))?;
         Ok(self)
     }
 
@@ -81,11 +225,11 @@ impl DatabaseAdapter for Connection {
         trace!("Connection create_views function.");
         info!("Creating views.");
         self.conn
-            .query_drop(include_str!("../sql/view-descriptor.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/view-descriptor.sql")))?;
         self.conn
-            .query_drop(include_str!("../sql/view-finalboard.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/view-finalboard.sql")))?;
         self.conn
-            .query_drop(include_str!("../sql/view-piecesleft.sql"))?;
+            .query_drop(self.names.sql(include_str!("../sql/view-piecesleft.sql")))?;
         Ok(self)
     }
 
@@ -99,18 +243,138 @@ impl DatabaseAdapter for Connection {
             .create_ruleset()?
             .create_game()?
             .create_move()?
-            .create_views()?;
+            .create_views()?
+            .migrate()?;
         info!("Database created correctly.");
         Ok(self)
     }
 
-    fn initialize_database(db_url: &str, rebuild: bool) -> Result<Self, Self::Error> {
+    fn prewarm_cache(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection prewarm_cache function.");
+        info!("Loading the existing IDs into the cache.");
+        let cache = &mut self.cache;
+        self.conn
+            .query_iter(self.names.sql(include_str!("../sql/select-players.sql")))?
+            .try_for_each(|row| {
+                let (id, name) = mysql::from_row_opt::<(u64, String)>(row?)
+                    .map_err(|e| mysql::Error::FromRowError(e.0))?;
+                cache.players.insert(name, id);
+                Ok::<_, mysql::Error>(())
+            })?;
+        self.conn
+            .query_iter(self.names.sql(include_str!("../sql/select-openings.sql")))?
+            .try_for_each(|row| {
+                let (id, name) = mysql::from_row_opt::<(u64, String)>(row?)
+                    .map_err(|e| mysql::Error::FromRowError(e.0))?;
+                cache.openings.insert(name, id);
+                Ok::<_, mysql::Error>(())
+            })?;
+        self.conn
+            .query_iter(self.names.sql(include_str!("../sql/select-rulesets.sql")))?
+            .try_for_each(|row| {
+                let (id, name, url) = mysql::from_row_opt::<(u64, String, String)>(row?)
+                    .map_err(|e| mysql::Error::FromRowError(e.0))?;
+                cache.rulesets.insert((name, url), id);
+                Ok::<_, mysql::Error>(())
+            })?;
+        self.conn
+            .query_iter(self.names.sql(include_str!("../sql/select-finalconfs.sql")))?
+            .try_for_each(|row| {
+                let mut row = row?;
+                let final_configuration = BoardConfiguration {
+                    black_left: row.take(1).unwrap_or_default(),
+                    white_left: row.take(2).unwrap_or_default(),
+                    rows: std::array::from_fn(|i| row.take(i + 3).unwrap_or_default()),
+                };
+                cache
+                    .final_configurations
+                    .insert(final_configuration, row.take(0).unwrap_or_default());
+                Ok::<_, mysql::Error>(())
+            })?;
+        self.cache.log_sizes();
+        Ok(self)
+    }
+
+    fn defer_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection defer_constraints function.");
+        info!("Dropping the constraints of the Game and Move tables.");
+        let tables = params! {
+            "game" => self.names.table("Game"),
+            "move" => self.names.table("Move"),
+        };
+        let foreign_keys: Vec<(String, String)> = self.conn.exec(
+            include_str!("../sql/select-constraints.sql"),
+            tables.clone(),
+        )?;
+        for (table, constraint) in foreign_keys {
+            self.conn.query_drop(format!(
+                "ALTER TABLE `{table}` DROP FOREIGN KEY `{constraint}`;"
+            ))?;
+        }
+        let indexes: Vec<(String, String)> = self
+            .conn
+            .exec(include_str!("../sql/select-indexes.sql"), tables)?;
+        for (table, index) in indexes {
+            self.conn
+                .query_drop(format!("ALTER TABLE `{table}` DROP INDEX `{index}`;"))?;
+        }
+        self.conn
+            .query_drop(self.names.sql("ALTER TABLE Move DROP PRIMARY KEY;"))?;
+        Ok(self)
+    }
+
+    fn constraint_violations(&mut self) -> Result<Vec<(String, u64)>, Self::Error> {
+        self.conn
+            .query(self.names.sql(include_str!("../sql/select-violations.sql")))
+    }
+
+    fn add_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection add_constraints function.");
+        self.conn
+            .query_drop(self.names.sql(include_str!("../sql/add-constraints.sql")))?;
+        Ok(self)
+    }
+
+    fn schema_version(&mut self) -> Result<u32, Self::Error> {
+        if self.conn.exec_first::<u64, _, _>(
+            include_str!("../sql/select-schema-version-table.sql"),
+            params! { "table" => self.names.table("SchemaVersion") },
+        )? != Some(1)
+        {
+            return Ok(0);
+        }
+        Ok(self
+            .conn
+            .query_first(
+                self.names
+                    .sql(include_str!("../sql/select-schema-version.sql")),
+            )?
+            .unwrap_or_default())
+    }
+
+    fn apply_migration(&mut self, version: u32) -> Result<&mut Self, Self::Error> {
+        trace!("Connection apply_migration function.");
+        self.conn
+            .query_drop(self.names.sql(MYSQL_MIGRATIONS[version as usize - 1]))?;
+        self.conn.exec_drop(
+            self.names
+                .sql(include_str!("../sql/insert-schema-version.sql")),
+            params! { version },
+        )?;
+        Ok(self)
+    }
+
+    fn initialize_database(db_url: &str, names: Names, rebuild: bool) -> Result<Self, Self::Error> {
         trace!("Connection initialize_database function.");
         info!("Initializing connection.");
-        let mut connection = Self::new(db_url)?;
+        let mut connection = Self::new(db_url, names)?;
         if rebuild {
             info!("Rebuilding database.");
-        } else if connection.conn.select_db("lichess").is_ok() {
+        } else if connection
+            .conn
+            .select_db(&connection.names.database)
+            .is_ok()
+        {
             info!("Database already exists, proceeding.");
             return Ok(connection);
         } else {
@@ -120,15 +384,101 @@ impl DatabaseAdapter for Connection {
         Ok(connection)
     }
 
+    fn start_run(&mut self, run: &IngestionRun) -> Result<u64, Self::Error> {
+        trace!("Connection start_run function.");
+        let run_id = self
+            .conn
+            .exec_iter(
+                self.names.sql(include_str!("../sql/insert-run.sql")),
+                params! {
+                    "version" => &run.version,
+                    "features" => &run.features,
+                    "arguments" => &run.arguments,
+                    "sample_seed" => &run.sample_seed,
+                    "sample_size" => run.sample_size,
+                    "source_file" => &run.source_file,
+                },
+            )?
+            .last_insert_id()
+            .expect("The query is a run insertion query and thus must return an insert id.");
+        self.run_id = Some(run_id);
+        Ok(run_id)
+    }
+
+    fn finish_run(&mut self, run: &IngestionRun) -> Result<&mut Self, Self::Error> {
+        trace!("Connection finish_run function.");
+        if let Some(run_id) = self.run_id {
+            self.conn.exec_drop(
+                self.names.sql(include_str!("../sql/update-run.sql")),
+                params! {
+                    run_id,
+                    "games_parsed" => run.games_parsed,
+                    "games_inserted" => run.games_inserted,
+                    "games_rejected" => run.games_rejected,
+                    "games_with_errors" => run.games_with_errors,
+                },
+            )?;
+        }
+        Ok(self)
+    }
+
+    fn source_runs(&mut self, source_file: &str) -> Result<Vec<u64>, Self::Error> {
+        self.conn.exec(
+            self.names
+                .sql(include_str!("../sql/select-source-runs.sql")),
+            params! { source_file },
+        )
+    }
+
+    fn purge_run(&mut self, run_id: u64) -> Result<u64, Self::Error> {
+        trace!("Connection purge_run function.");
+        self.conn.query_drop("START TRANSACTION;")?;
+        match self.purge_run_rows(run_id) {
+            Ok(games) => {
+                self.conn.query_drop("COMMIT;")?;
+                Ok(games)
+            }
+            Err(e) => {
+                if let Err(rollback) = self.conn.query_drop("ROLLBACK;") {
+                    warn!("Rollback error: {rollback}");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn delete_orphans(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection delete_orphans function.");
+        self.conn
+            .query_drop(self.names.sql(include_str!("../sql/delete-orphans.sql")))?;
+        Ok(self)
+    }
+
+    fn select_table(&mut self, query: &str) -> Result<Table, Self::Error> {
+        trace!("Connection select_table function.");
+        let mut result = self.conn.query_iter(self.names.sql(query))?;
+        let columns = result
+            .columns()
+            .as_ref()
+            .iter()
+            .map(|column| column.name_str().into_owned())
+            .collect();
+        let rows = result
+            .by_ref()
+            .map(|row| Ok(row?.unwrap().into_iter().map(value_text).collect()))
+            .collect::<Result<_, Self::Error>>()?;
+        Ok(Table { columns, rows })
+    }
+
     fn insert_final_configuration(
         &mut self,
         final_configuration: &BoardConfiguration,
     ) -> Result<u64, Self::Error> {
         let params = final_configuration.as_params();
-        match self
-            .conn
-            .exec_iter(include_str!("../sql/insert-finalconf.sql"), &params)
-        {
+        match self.conn.exec_iter(
+            self.names.sql(include_str!("../sql/insert-finalconf.sql")),
+            &params,
+        ) {
             Ok(result) => {
                 return Ok(result
                 .last_insert_id()
@@ -136,7 +486,7 @@ impl DatabaseAdapter for Connection {
             }
             Err(e) => match e {
                 Self::Error::MySqlError(mut mse) => {
-                    if !mse.message.starts_with("Duplicate entry") {
+                    if mse.code != ER_DUP_ENTRY {
                         mse.message
                             .push_str(&format!(" (FinalConfiguration: {:?})", final_configuration));
                         return Err(Self::Error::MySqlError(mse));
@@ -146,13 +496,13 @@ impl DatabaseAdapter for Connection {
             },
         }
         Ok(self.conn
-        .exec_first::<u64, _, _>(include_str!("../sql/select-finalconf.sql"), params)?
+        .exec_first::<u64, _, _>(self.names.sql(include_str!("../sql/select-finalconf.sql")), params)?
         .expect("There was an error when trying to insert a final configuration so it must have existed already."))
     }
 
     fn insert_opening(&mut self, opening: &Opening, eco: Eco) -> Result<u64, Self::Error> {
         match self.conn.exec_iter(
-            include_str!("../sql/insert-opening.sql"),
+            self.names.sql(include_str!("../sql/insert-opening.sql")),
             opening.as_insert_params(eco),
         ) {
             Ok(result) => {
@@ -162,7 +512,7 @@ impl DatabaseAdapter for Connection {
             }
             Err(e) => match e {
                 Self::Error::MySqlError(mut mse) => {
-                    if !mse.message.starts_with("Duplicate entry") {
+                    if mse.code != ER_DUP_ENTRY {
                         mse.message.push_str(" (Opening: ");
                         mse.message.push_str(&opening.0);
                         mse.message.push(' ');
@@ -176,7 +526,7 @@ impl DatabaseAdapter for Connection {
         }
         Ok(self.conn
         .exec_first::<u64, _, _>(
-            include_str!("../sql/select-opening.sql"),
+            self.names.sql(include_str!("../sql/select-opening.sql")),
             opening.as_select_params(),
         )?
         .expect(
@@ -186,10 +536,10 @@ impl DatabaseAdapter for Connection {
 
     fn insert_player(&mut self, player: &Player) -> Result<u64, Self::Error> {
         let params = player.as_params();
-        match self
-            .conn
-            .exec_iter(include_str!("../sql/insert-player.sql"), &params)
-        {
+        match self.conn.exec_iter(
+            self.names.sql(include_str!("../sql/insert-player.sql")),
+            &params,
+        ) {
             Ok(result) => {
                 return Ok(result.last_insert_id().expect(
                     "The query is a player insertion query and thus must return an insert id.",
@@ -197,7 +547,7 @@ impl DatabaseAdapter for Connection {
             }
             Err(e) => match e {
                 Self::Error::MySqlError(mut mse) => {
-                    if !mse.message.starts_with("Duplicate entry") {
+                    if mse.code != ER_DUP_ENTRY {
                         mse.message.push_str(" (Player: ");
                         mse.message.push_str(&player.0);
                         mse.message.push(')');
@@ -208,7 +558,7 @@ impl DatabaseAdapter for Connection {
             },
         }
         Ok(self.conn
-        .exec_first::<u64, _, _>(include_str!("../sql/select-player.sql"), params)?
+        .exec_first::<u64, _, _>(self.names.sql(include_str!("../sql/select-player.sql")), params)?
         .expect(
             "There was an error when trying to insert a player so it must have existed already.",
         ))
@@ -216,7 +566,7 @@ impl DatabaseAdapter for Connection {
 
     fn insert_ruleset(&mut self, ruleset: &RuleSet) -> Result<u64, Self::Error> {
         match self.conn.exec_iter(
-            include_str!("../sql/insert-ruleset.sql"),
+            self.names.sql(include_str!("../sql/insert-ruleset.sql")),
             ruleset.as_insert_params(),
         ) {
             Ok(result) => {
@@ -226,7 +576,7 @@ impl DatabaseAdapter for Connection {
             }
             Err(e) => match e {
                 Self::Error::MySqlError(mut mse) => {
-                    if !mse.message.starts_with("Duplicate entry") {
+                    if mse.code != ER_DUP_ENTRY {
                         mse.message.push_str(" (RuleSet: ");
                         mse.message.push_str(&ruleset.name);
                         mse.message.push_str(" - ");
@@ -240,7 +590,7 @@ impl DatabaseAdapter for Connection {
         }
         Ok(self.conn
         .exec_first::<u64, _, _>(
-            include_str!("../sql/select-ruleset.sql"),
+            self.names.sql(include_str!("../sql/select-ruleset.sql")),
             ruleset.as_select_params(),
         )?
         .expect(
@@ -260,37 +610,44 @@ impl DatabaseAdapter for Connection {
         Ok(self
             .conn
             .exec_iter(
-                include_str!("../sql/insert-game.sql"),
-                game.as_params(ruleset_id, opening_id, fc_id, white_id, black_id),
+                self.names.sql(include_str!("../sql/insert-game.sql")),
+                game.as_params(
+                    ruleset_id,
+                    opening_id,
+                    fc_id,
+                    white_id,
+                    black_id,
+                    self.run_id,
+                ),
             )?
             .last_insert_id()
             .expect("The query is a game insertion query and thus must return an insert id."))
     }
 
     fn insert_game_data(&mut self, game: &Game) -> Result<u64, Self::Error> {
-        let ruleset_id = self.insert_ruleset(&game.ruleset)?;
+        let ruleset_id = self.ruleset_id(&game.ruleset)?;
         let opening_id = if game.opening.0.is_empty() {
             None
         } else {
-            Some(self.insert_opening(&game.opening, game.eco)?)
+            Some(self.opening_id(&game.opening, game.eco)?)
         };
-        let fc_id = self.insert_final_configuration(&game.final_conf)?;
+        let fc_id = self.final_configuration_id(&game.final_conf)?;
         let white_id = if game.white.0.is_empty() {
             None
         } else {
-            Some(self.insert_player(&game.white)?)
+            Some(self.player_id(&game.white)?)
         };
         let black_id = if game.black.0.is_empty() {
             None
         } else {
-            Some(self.insert_player(&game.black)?)
+            Some(self.player_id(&game.black)?)
         };
         self.insert_game(game, ruleset_id, opening_id, fc_id, white_id, black_id)
     }
 
     fn insert_move(&mut self, r#move: &Move, game_id: u64) -> Result<&mut Self, Self::Error> {
         self.conn.exec_drop(
-            include_str!("../sql/insert-move.sql"),
+            self.names.sql(include_str!("../sql/insert-move.sql")),
             r#move.as_params(game_id),
         )?;
         Ok(self)
@@ -298,14 +655,434 @@ impl DatabaseAdapter for Connection {
 
     fn insert_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error> {
         self.conn.exec_batch(
-            include_str!("../sql/insert-move.sql"),
+            self.names.sql(include_str!("../sql/insert-move.sql")),
             moves.iter().map(|r#move| r#move.as_params(game_id)),
         )?;
         Ok(self)
     }
 
+    fn insert_packed_moves(
+        &mut self,
+        moves: &[Move],
+        game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        self.conn.exec_drop(
+            self.names.sql(include_str!("../sql/update-game-moves.sql")),
+            params! { game_id, "moves" => pack(moves) },
+        )?;
+        Ok(self)
+    }
+
+    fn move_storage(&self) -> MoveStorage {
+        self.move_storage
+    }
+
+    fn update_rollups(
+        &mut self,
+        first_game_id: u64,
+        last_game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        self.conn.exec_drop(
+            self.names.sql(include_str!("../sql/add-opening-stats.sql")),
+            (first_game_id, last_game_id),
+        )?;
+        self.conn.exec_drop(
+            self.names.sql(include_str!("../sql/add-endgame-stats.sql")),
+            (first_game_id, last_game_id),
+        )?;
+        Ok(self)
+    }
+
     fn insert_all(&mut self, data: &Data) -> Result<&mut Self, Self::Error> {
-        let game_id = self.insert_game_data(&data.game)?;
-        self.insert_moves(&data.moves, game_id)
+        self.transaction(|connection| {
+            let game_id = connection.insert_game_data(&data.game)?;
+            connection
+                .store_moves(&data.moves, game_id)?
+                .update_rollups(game_id, game_id)?;
+            Ok(())
+        })?;
+        Ok(self)
+    }
+
+    fn insert_batch(&mut self, batch: &[(Game, Vec<Move>)]) -> Result<&mut Self, Self::Error> {
+        self.transaction(|connection| {
+            let mut cache = std::mem::take(&mut connection.cache);
+            let result = connection.insert_batch_rows(batch, &mut cache);
+            connection.cache = cache;
+            result
+        })?;
+        Ok(self)
+    }
+}
+
+impl Connection {
+    /// Opens the connection again with the options it was first opened with, selecting the database.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if the connection or the selection fail.
+    fn reconnect(&mut self) -> Result<(), mysql::Error> {
+        trace!("Connection reconnect function.");
+        info!("Reconnecting to the database.");
+        self.conn = Conn::new(self.opts.clone())?;
+        self.conn.select_db(&self.names.database)?;
+        Ok(())
+    }
+
+    /// Runs insertions in a transaction, so that either all of them are kept or none, trying them again after a growing backoff while they fail with a transient error, reconnecting first if the connection was lost. The IDs cached are cleared whenever the transaction is rolled back, since they may not exist anymore.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if the insertions fail with a permanent error, or with a transient one after every retry.
+    fn transaction<F>(&mut self, mut insertions: F) -> Result<(), mysql::Error>
+    where
+        F: FnMut(&mut Self) -> Result<(), mysql::Error>,
+    {
+        let mut backoff = BACKOFF;
+        let mut retries = 0;
+        loop {
+            let result = self
+                .conn
+                .query_drop("START TRANSACTION;")
+                .and_then(|()| insertions(self))
+                .and_then(|()| self.conn.query_drop("COMMIT;"));
+            let Err(e) = result else {
+                return Ok(());
+            };
+            self.cache.clear();
+            let kind = error_kind(&e);
+            if kind != ErrorKind::Connection
+                && let Err(rollback) = self.conn.query_drop("ROLLBACK;")
+            {
+                warn!("Rollback error: {rollback}");
+                return Err(e);
+            }
+            if retries == RETRIES || !matches!(kind, ErrorKind::Transient | ErrorKind::Connection) {
+                return Err(e);
+            }
+            retries += 1;
+            warn!(
+                "Transient database error, trying again in {} ms ({retries} of {RETRIES}): {e}",
+                backoff.as_millis()
+            );
+            sleep(backoff);
+            backoff *= 2;
+            if kind == ErrorKind::Connection
+                && let Err(e) = self.reconnect()
+            {
+                warn!("Reconnection error: {e}");
+            }
+        }
+    }
+
+    /// Deletes the games of a run along with their moves and marks the run as purged, returning the number of games deleted, without starting a transaction.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if any of the deletions fail.
+    fn purge_run_rows(&mut self, run_id: u64) -> Result<u64, mysql::Error> {
+        self.conn.exec_drop(
+            self.names
+                .sql(include_str!("../sql/subtract-opening-stats.sql")),
+            (run_id,),
+        )?;
+        self.conn.exec_drop(
+            self.names
+                .sql(include_str!("../sql/subtract-endgame-stats.sql")),
+            (run_id,),
+        )?;
+        self.conn.query_drop(
+            self.names
+                .sql(include_str!("../sql/delete-empty-stats.sql")),
+        )?;
+        self.conn.exec_drop(
+            self.names.sql(include_str!("../sql/delete-run-moves.sql")),
+            params! { run_id },
+        )?;
+        let games = self
+            .conn
+            .exec_iter(
+                self.names.sql(include_str!("../sql/delete-run-games.sql")),
+                params! { run_id },
+            )?
+            .affected_rows();
+        self.conn.exec_drop(
+            self.names.sql(include_str!("../sql/purge-run.sql")),
+            params! { run_id },
+        )?;
+        Ok(games)
+    }
+
+    /// Inserts a value into its table, or finds it if it already existed, with an `INSERT ... ON DUPLICATE KEY UPDATE` query, renamed before running it, that sets its ID as the last insert ID either way.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if the insertion fails.
+    fn upsert(&mut self, query: &str, params: Params) -> Result<u64, mysql::Error> {
+        Ok(self
+            .conn
+            .exec_iter(self.names.sql(query), params)?
+            .last_insert_id()
+            .expect("The query is an upsert query and thus must return an insert id."))
+    }
+
+    /// Inserts a batch of games and their moves with multi-row insertions, or packs the moves of every game into it if they are stored packed, inserting each ruleset, opening, final configuration and player that is not cached with an upsert. The IDs of the games are taken as consecutive from the first one, which holds as long as no other client inserts games at the same time.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if any of the insertions fail.
+    fn insert_batch_rows(
+        &mut self,
+        batch: &[(Game, Vec<Move>)],
+        cache: &mut IdCaches,
+    ) -> Result<(), mysql::Error> {
+        let mut game_ids = Vec::with_capacity(batch.len());
+        for chunk in batch.chunks(GAME_ROWS) {
+            let mut values = Vec::with_capacity(chunk.len() * GAME_PARAMS.len());
+            for (game, _) in chunk {
+                let ruleset_id = cache.rulesets.get_or_insert(
+                    &(game.ruleset.name.clone(), game.ruleset.url.clone()),
+                    || {
+                        self.upsert(
+                            include_str!("../sql/upsert-ruleset.sql"),
+                            game.ruleset.as_insert_params(),
+                        )
+                        .map_err(|e| {
+                            with_context(
+                                e,
+                                format!(
+                                    "RuleSet: {} - {}",
+                                    game.ruleset.name,
+                                    game.ruleset.kind.as_str()
+                                ),
+                            )
+                        })
+                    },
+                )?;
+                let opening_id = if game.opening.0.is_empty() {
+                    None
+                } else {
+                    Some(cache.openings.get_or_insert(&game.opening.0, || {
+                        self.upsert(
+                            include_str!("../sql/upsert-opening.sql"),
+                            game.opening.as_insert_params(game.eco),
+                        )
+                        .map_err(|e| {
+                            with_context(
+                                e,
+                                format!(
+                                    "Opening: {} {}{:02}",
+                                    game.opening.0,
+                                    game.eco.0.as_char(),
+                                    game.eco.1.get()
+                                ),
+                            )
+                        })
+                    })?)
+                };
+                let fc_id = cache
+                    .final_configurations
+                    .get_or_insert(&game.final_conf, || {
+                        self.upsert(
+                            include_str!("../sql/upsert-finalconf.sql"),
+                            game.final_conf.as_params(),
+                        )
+                        .map_err(|e| {
+                            with_context(e, format!("FinalConfiguration: {:?}", game.final_conf))
+                        })
+                    })?;
+                let mut player_id = |player: &Player| {
+                    cache.players.get_or_insert(&player.0, || {
+                        self.upsert(include_str!("../sql/upsert-player.sql"), player.as_params())
+                            .map_err(|e| with_context(e, format!("Player: {}", player.0)))
+                    })
+                };
+                let white_id = if game.white.0.is_empty() {
+                    None
+                } else {
+                    Some(player_id(&game.white)?)
+                };
+                let black_id = if game.black.0.is_empty() {
+                    None
+                } else {
+                    Some(player_id(&game.black)?)
+                };
+                push_positional(
+                    &mut values,
+                    game.as_params(
+                        ruleset_id,
+                        opening_id,
+                        fc_id,
+                        white_id,
+                        black_id,
+                        self.run_id,
+                    ),
+                    &GAME_PARAMS,
+                );
+            }
+            let first_id = self
+                .conn
+                .exec_iter(
+                    multi_row_query(
+                        &self.names.sql(include_str!("../sql/insert-games.sql")),
+                        GAME_PARAMS.len(),
+                        chunk.len(),
+                    ),
+                    values,
+                )?
+                .last_insert_id()
+                .expect("The query is a game insertion query and thus must return an insert id.");
+            // The IDs of a multi-row insertion are increasing but not always consecutive: with
+            // `innodb_autoinc_lock_mode = 2`, the default of MySQL 8, concurrent insertions may
+            // take IDs in between, so they are read back instead of assumed.
+            let chunk_ids: Vec<u64> = self.conn.exec(
+                self.names.sql(include_str!("../sql/select-run-games.sql")),
+                (self.run_id, first_id, chunk.len()),
+            )?;
+            for range in chunk_ids.chunk_by(|previous, next| previous + 1 == *next) {
+                self.update_rollups(range[0], range[range.len() - 1])?;
+            }
+            game_ids.extend(chunk_ids);
+        }
+
+        if self.move_storage == MoveStorage::Packed {
+            self.conn.exec_batch(
+                self.names.sql(include_str!("../sql/update-game-moves.sql")),
+                batch
+                    .iter()
+                    .zip(game_ids)
+                    .map(|((_, moves), game_id)| params! { game_id, "moves" => pack(moves) }),
+            )?;
+            return Ok(());
+        }
+        let moves = batch
+            .iter()
+            .zip(game_ids)
+            .flat_map(|((_, moves), game_id)| moves.iter().map(move |r#move| (r#move, game_id)))
+            .collect::<Vec<_>>();
+        for chunk in moves.chunks(MOVE_ROWS) {
+            let mut values = Vec::with_capacity(chunk.len() * MOVE_PARAMS.len());
+            for (r#move, game_id) in chunk {
+                push_positional(&mut values, r#move.as_params(*game_id), &MOVE_PARAMS);
+            }
+            self.conn.exec_drop(
+                multi_row_query(
+                    &self.names.sql(include_str!("../sql/insert-moves.sql")),
+                    MOVE_PARAMS.len(),
+                    chunk.len(),
+                ),
+                values,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+impl Connection {
+    /// Loads a CSV file into its table, renaming the table of the query and replacing its file placeholder with the path.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if the loading fails.
+    fn load_csv_file(
+        &mut self,
+        query: &str,
+        dir: &Path,
+        name: &str,
+    ) -> Result<&mut Self, mysql::Error> {
+        info!("Loading {name}.");
+        let file = dir
+            .join(name)
+            .to_string_lossy()
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'");
+        self.conn
+            .query_drop(self.names.sql(query).replace(":file", &file))?;
+        Ok(self)
+    }
+
+    /// Loads the CSV files exported to the directory into the database with `LOAD DATA LOCAL INFILE`, following the order of the dependencies between the tables. Since the IDs were assigned when exporting, the tables must be empty.
+    ///
+    /// # Errors
+    /// Will return [`mysql::Error`] if the tables are not empty, the server doesn't allow loading local files or any of the loadings fail.
+    pub fn load_csv(&mut self, dir: &Path) -> Result<&mut Self, mysql::Error> {
+        trace!("Connection load_csv function.");
+        if self
+            .conn
+            .query_first::<u64, _>(self.names.sql("SELECT COUNT(*) FROM Game"))?
+            != Some(0)
+        {
+            return Err(mysql::Error::IoError(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "The database must be empty to load the exported CSV files, consider using the flag \"-r\"/\"--rebuild\".",
+            )));
+        }
+        self.conn
+            .set_local_infile_handler(Some(LocalInfileHandler::new(|file, writer| {
+                let path =
+                    from_utf8(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                io::copy(&mut File::open(path)?, writer)?;
+                Ok(())
+            })));
+        self.load_csv_file(
+            include_str!("../sql/load-player.sql"),
+            dir,
+            super::csv::PLAYER_CSV,
+        )?
+        .load_csv_file(
+            include_str!("../sql/load-opening.sql"),
+            dir,
+            super::csv::OPENING_CSV,
+        )?
+        .load_csv_file(
+            include_str!("../sql/load-ruleset.sql"),
+            dir,
+            super::csv::RULESET_CSV,
+        )?
+        .load_csv_file(
+            include_str!("../sql/load-finalconf.sql"),
+            dir,
+            super::csv::FINAL_CONFIGURATION_CSV,
+        )?
+        .load_csv_file(
+            include_str!("../sql/load-game.sql"),
+            dir,
+            super::csv::GAME_CSV,
+        )?
+        .load_csv_file(
+            include_str!("../sql/load-move.sql"),
+            dir,
+            super::csv::MOVE_CSV,
+        )?;
+        self.conn.set_local_infile_handler(None);
+        info!("Adding the loaded games to the rollup tables.");
+        self.update_rollups(1, u64::from(u32::MAX))?;
+        info!("CSV files loaded correctly.");
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mysql::{DriverError, MySqlError};
+    use pretty_assertions::assert_eq;
+
+    use super::{ErrorKind, error_kind};
+
+    /// Creates an error of the server with a code.
+    fn server_error(code: u16) -> mysql::Error {
+        mysql::Error::MySqlError(MySqlError {
+            state: String::from("HY000"),
+            message: String::new(),
+            code,
+        })
+    }
+
+    /// Tests that the errors are classified by their code instead of their message.
+    #[test]
+    fn error_kind_test() {
+        assert_eq!(error_kind(&server_error(1062)), ErrorKind::Duplicate);
+        assert_eq!(error_kind(&server_error(1213)), ErrorKind::Transient);
+        assert_eq!(error_kind(&server_error(2006)), ErrorKind::Connection);
+        assert_eq!(error_kind(&server_error(1452)), ErrorKind::Permanent);
+        assert_eq!(
+            error_kind(&mysql::Error::DriverError(DriverError::PacketOutOfSync)),
+            ErrorKind::Connection
+        );
     }
-}
\ No newline at end of file
+}
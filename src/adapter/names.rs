@@ -0,0 +1,210 @@
+//! Module of the names of the database and its tables, which rewrites the SQL scripts so that several datasets can live in one server or one database.
+
+use std::borrow::Cow;
+
+/// Default name of the database, which is the one written in the SQL scripts.
+pub const DEFAULT_DATABASE_NAME: &str = "lichess";
+
+/// Tables and views of the database, whose names are prefixed.
+const TABLES: [&str; 13] = [
+    "Game",
+    "Move",
+    "Player",
+    "Opening",
+    "RuleSet",
+    "FinalConfiguration",
+    "SchemaVersion",
+    "IngestionRun",
+    "OpeningStats",
+    "EndgameStats",
+    "MoveDescriptor",
+    "FinalBoard",
+    "PiecesLeft",
+];
+
+/// Keywords after which a name is the one of a table or view, except `UPDATE` in `ON DUPLICATE KEY UPDATE`.
+const TABLE_KEYWORDS: [&str; 9] = [
+    "FROM",
+    "JOIN",
+    "INTO",
+    "UPDATE",
+    "TABLE",
+    "EXISTS",
+    "REFERENCES",
+    "VIEW",
+    "COPY",
+];
+
+/// Whether a backslash escapes the next character of a string literal, as it does in MySQL.
+const BACKSLASH_ESCAPES: bool = cfg!(any(
+    feature = "time-mysql",
+    feature = "chrono-mysql",
+    feature = "time-diesel",
+    feature = "chrono-diesel"
+));
+
+/// Finds the end of the string literal or quoted identifier at the start of a query, just past its closing quote.
+fn quoted_end(query: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in query.char_indices().skip(1) {
+        if c == quote && !escaped {
+            return i + 1;
+        }
+        escaped = BACKSLASH_ESCAPES && quote != '`' && c == '\\' && !escaped;
+    }
+    query.len()
+}
+
+/// The name of the database and the prefix of its tables and views.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Names {
+    /// Name of the database, or of the schema with PostgreSQL.
+    pub database: String,
+    /// Prefix prepended to the name of every table and view.
+    pub table_prefix: String,
+}
+
+impl Default for Names {
+    fn default() -> Self {
+        Self::new(DEFAULT_DATABASE_NAME, "")
+    }
+}
+
+impl Names {
+    /// Creates the names of a database and its tables.
+    pub fn new(database: &str, table_prefix: &str) -> Self {
+        Self {
+            database: database.to_owned(),
+            table_prefix: table_prefix.to_owned(),
+        }
+    }
+
+    /// Gets the name of a table or view with its prefix.
+    #[cfg_attr(
+        any(feature = "time-diesel", feature = "chrono-diesel"),
+        allow(dead_code)
+    )]
+    pub fn table(&self, name: &str) -> String {
+        format!("{}{name}", self.table_prefix)
+    }
+
+    /// Rewrites an SQL script, replacing the default name of the database and prefixing the name of every table and view where it is used as one, which is after a keyword that names a table or before a dot, without touching string literals, quoted identifiers, comments or columns named like a table.
+    pub fn sql<'a>(&self, query: &'a str) -> Cow<'a, str> {
+        if self.database == DEFAULT_DATABASE_NAME && self.table_prefix.is_empty() {
+            return Cow::Borrowed(query);
+        }
+        let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let mut result = String::with_capacity(query.len() + 16 * self.table_prefix.len());
+        let mut rest = query;
+        let mut words = ("", "");
+        let mut qualified = false;
+        while let Some(c) = rest.chars().next() {
+            let end = match c {
+                '\'' | '"' | '`' => quoted_end(rest, c),
+                '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+                c if is_word(c) => {
+                    let end = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+                    let word = &rest[..end];
+                    let dotted = rest[end..].starts_with('.');
+                    if word.eq_ignore_ascii_case(DEFAULT_DATABASE_NAME) {
+                        result.push_str(&self.database);
+                        qualified = false;
+                    } else {
+                        let table_position = dotted
+                            || TABLE_KEYWORDS
+                                .iter()
+                                .any(|keyword| words.1.eq_ignore_ascii_case(keyword))
+                                && !(words.0.eq_ignore_ascii_case("KEY")
+                                    && words.1.eq_ignore_ascii_case("UPDATE"));
+                        if !qualified
+                            && table_position
+                            && TABLES.iter().any(|table| word.eq_ignore_ascii_case(table))
+                        {
+                            result.push_str(&self.table_prefix);
+                        }
+                        result.push_str(word);
+                        words = (words.1, word);
+                        qualified = dotted;
+                    }
+                    rest = &rest[end..];
+                    continue;
+                }
+                c => c.len_utf8(),
+            };
+            qualified &= c == '.';
+            result.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+        Cow::Owned(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::ValueEnum;
+    use pretty_assertions::assert_eq;
+
+    use super::{BACKSLASH_ESCAPES, Names, TABLES};
+    use crate::analysis::{Analysis, Parameters};
+
+    /// Tests that only the database and the tables are renamed, and not the literals, columns or comments.
+    #[test]
+    fn sql_test() {
+        let names = Names::new("lichess_2024_01", "sample_");
+        assert_eq!(
+            names.sql("USE lichess;\n-- Game of the Move\nSELECT g.GameId, 'Italian Game', `Move` FROM game g JOIN Move ON Move.GameId = g.GameId WHERE Kind = 'Game''s';"),
+            "USE lichess_2024_01;\n-- Game of the Move\nSELECT g.GameId, 'Italian Game', `Move` FROM sample_game g JOIN sample_Move ON sample_Move.GameId = g.GameId WHERE Kind = 'Game''s';"
+        );
+        assert_eq!(names.table("Game"), "sample_Game");
+        let query = "SELECT * FROM Game;";
+        assert_eq!(Names::default().sql(query), query);
+    }
+
+    /// Tests that the columns named like a table and the literals with escaped quotes are kept as they are.
+    #[test]
+    fn column_sql_test() {
+        let names = Names::new("lichess", "s_");
+        assert_eq!(
+            names.sql("INSERT INTO lichess.Game (Player) SELECT g.Opening, Move.Player AS Move FROM Game g JOIN Move ON Move.GameId = g.GameId ON DUPLICATE KEY UPDATE Player = 1;"),
+            "INSERT INTO lichess.s_Game (Player) SELECT g.Opening, s_Move.Player AS Move FROM s_Game g JOIN s_Move ON s_Move.GameId = g.GameId ON DUPLICATE KEY UPDATE Player = 1;"
+        );
+        let expected = if BACKSLASH_ESCAPES {
+            r"SELECT 'It\'s', 'FROM Game' FROM s_Game;"
+        } else {
+            r"SELECT 'It\'s', 'FROM s_Game' FROM Game;"
+        };
+        assert_eq!(
+            names.sql(r"SELECT 'It\'s', 'FROM Game' FROM Game;"),
+            expected
+        );
+    }
+
+    /// Tests that the queries of the reports read every table with its prefix and keep the names of their columns.
+    #[test]
+    fn report_sql_test() {
+        let names = Names::new("lichess", "s_");
+        let parameters = Parameters {
+            from: Some("2024-01-01".to_owned()),
+            to: None,
+            elo_buckets: vec![1400, 1600, 2200],
+            openings: vec!["Sicilian Defense".to_owned()],
+        };
+        for analysis in Analysis::value_variants() {
+            let query = parameters.query(*analysis);
+            let mut expected = query.clone();
+            for table in TABLES {
+                for keyword in ["FROM", "JOIN"] {
+                    expected = expected.replace(
+                        &format!("{keyword} {table} "),
+                        &format!("{keyword} s_{table} "),
+                    );
+                }
+            }
+            assert_eq!(names.sql(&query), expected, "{}", analysis.name());
+        }
+        assert_eq!(
+            names.sql("SELECT r.Name AS ruleset FROM RuleSet r;"),
+            "SELECT r.Name AS ruleset FROM s_RuleSet r;"
+        );
+    }
+}
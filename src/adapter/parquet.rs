@@ -0,0 +1,522 @@
+//! Module that exports the games and moves to Parquet files partitioned by month, with typed columns meant for columnar analytics engines instead of a relational database.
+//!
+//! The files follow a Hive-style layout, `<dir>/game/month=YYYY-MM/part-0.parquet` and `<dir>/move/month=YYYY-MM/part-0.parquet`, and the rows are buffered in batches so that the memory used stays bounded.
+
+use std::{
+    collections::HashMap,
+    fs::{File, create_dir_all},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use arrow_array::{
+    ArrayRef, RecordBatch,
+    builder::{
+        ArrayBuilder, BooleanBuilder, Float32Builder, Int8Builder, StringBuilder,
+        Time32SecondBuilder, TimestampSecondBuilder, UInt8Builder, UInt16Builder, UInt32Builder,
+        UInt64Builder,
+    },
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use log::{info, trace};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    errors::Result,
+    file::properties::WriterProperties,
+};
+
+use super::variant;
+use lichess::{
+    attributes::{BoardConfiguration, Eval, datetime::Datetime},
+    data::{Data, Game, Move},
+};
+
+/// Name of the directory of the Game partitions.
+pub const GAME_DIR: &str = "game";
+/// Name of the directory of the Move partitions.
+pub const MOVE_DIR: &str = "move";
+/// Number of games buffered before they are written to their file.
+const GAME_BATCH: usize = 8192;
+/// Number of moves buffered before they are written to their file.
+const MOVE_BATCH: usize = 65536;
+/// Maximum number of rows of each row group, which the writers keep in memory until they are full.
+const ROW_GROUP_SIZE: usize = 262_144;
+
+/// Role names of the pieces, in the order of their numbering in [`shakmaty::Role`].
+const ROLES: [&str; 5] = ["pawns", "knights", "bishops", "rooks", "queens"];
+
+/// Creates the [`Schema`] of the Game files.
+fn game_schema() -> SchemaRef {
+    let mut fields = vec![
+        Field::new("game_id", DataType::UInt64, false),
+        Field::new("event", DataType::Utf8, false),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("tournament", DataType::Utf8, true),
+        Field::new("white", DataType::Utf8, true),
+        Field::new("white_elo", DataType::UInt16, true),
+        Field::new("white_title", DataType::Utf8, true),
+        Field::new("black", DataType::Utf8, true),
+        Field::new("black_elo", DataType::UInt16, true),
+        Field::new("black_title", DataType::Utf8, true),
+        Field::new("start_time", DataType::UInt16, true),
+        Field::new("increment", DataType::UInt8, true),
+        Field::new("result", DataType::Utf8, false),
+        Field::new("termination", DataType::Utf8, false),
+        Field::new(
+            "datetime",
+            DataType::Timestamp(TimeUnit::Second, None),
+            false,
+        ),
+        Field::new("opening", DataType::Utf8, true),
+        Field::new("eco", DataType::Utf8, true),
+        Field::new("has_clock", DataType::Boolean, false),
+        Field::new("has_evaluations", DataType::Boolean, false),
+    ];
+    for color in ["white", "black"] {
+        for role in ROLES {
+            fields.push(Field::new(
+                format!("end_{color}_{role}"),
+                DataType::UInt8,
+                false,
+            ));
+        }
+    }
+    for row in 'a'..='h' {
+        fields.push(Field::new(format!("{row}_row"), DataType::UInt32, false));
+    }
+    Arc::new(Schema::new(fields))
+}
+
+/// Creates the [`Schema`] of the Move files.
+fn move_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("game_id", DataType::UInt64, false),
+        Field::new("num", DataType::UInt16, false),
+        Field::new("is_white", DataType::Boolean, false),
+        Field::new("moved_piece", DataType::UInt8, false),
+        Field::new("from_row", DataType::UInt8, false),
+        Field::new("from_column", DataType::UInt8, false),
+        Field::new("captured_piece", DataType::UInt8, false),
+        Field::new("to_row", DataType::UInt8, false),
+        Field::new("to_column", DataType::UInt8, false),
+        Field::new("promoted_piece", DataType::UInt8, false),
+        Field::new("is_check", DataType::Boolean, false),
+        Field::new("is_mate", DataType::Boolean, false),
+        Field::new("nag", DataType::UInt8, false),
+        Field::new("eval_float", DataType::Float32, true),
+        Field::new("eval_mate", DataType::Int8, true),
+        Field::new("clock", DataType::Time32(TimeUnit::Second), true),
+    ]))
+}
+
+/// Decodes the number of pieces of each role left from the codified value of a [`BoardConfiguration`], in the order of [`ROLES`].
+const fn pieces_left(left: u16) -> [u8; 5] {
+    [
+        ((left >> 12) & 0xF) as u8,
+        ((left >> 9) & 0x7) as u8,
+        ((left >> 6) & 0x7) as u8,
+        ((left >> 3) & 0x7) as u8,
+        (left & 0x7) as u8,
+    ]
+}
+
+/// Builders of the columns of a batch of the Game files.
+#[derive(Debug, Default)]
+struct GameColumns {
+    game_id: UInt64Builder,
+    event: StringBuilder,
+    kind: StringBuilder,
+    tournament: StringBuilder,
+    white: StringBuilder,
+    white_elo: UInt16Builder,
+    white_title: StringBuilder,
+    black: StringBuilder,
+    black_elo: UInt16Builder,
+    black_title: StringBuilder,
+    start_time: UInt16Builder,
+    increment: UInt8Builder,
+    result: StringBuilder,
+    termination: StringBuilder,
+    datetime: TimestampSecondBuilder,
+    opening: StringBuilder,
+    eco: StringBuilder,
+    has_clock: BooleanBuilder,
+    has_evaluations: BooleanBuilder,
+    pieces_left: [UInt8Builder; 10],
+    rows: [UInt32Builder; 8],
+}
+
+impl GameColumns {
+    /// Appends a [`Game`] to the columns.
+    fn append(&mut self, game_id: u64, game: &Game) {
+        let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_owned());
+        let (start_time, increment) = game.time_control.0.unzip();
+        self.game_id.append_value(game_id);
+        self.event.append_value(&game.ruleset.name);
+        self.kind.append_value(variant(game.ruleset.kind));
+        self.tournament.append_option(non_empty(&game.ruleset.url));
+        self.white.append_option(non_empty(&game.white.0));
+        self.white_elo.append_option(game.white_elo.0);
+        self.white_title
+            .append_option(game.white_title.map(variant));
+        self.black.append_option(non_empty(&game.black.0));
+        self.black_elo.append_option(game.black_elo.0);
+        self.black_title
+            .append_option(game.black_title.map(variant));
+        self.start_time.append_option(start_time);
+        self.increment.append_option(increment);
+        self.result.append_value(variant(game.result));
+        self.termination.append_value(variant(game.termination));
+        self.datetime
+            .append_value(Datetime::new(game.utc_date.0, game.utc_time.0).timestamp());
+        if game.opening.0.is_empty() {
+            self.opening.append_null();
+            self.eco.append_null();
+        } else {
+            self.opening.append_value(&game.opening.0);
+            self.eco.append_value(game.eco.to_string());
+        }
+        self.has_clock.append_value(game.has_clock);
+        self.has_evaluations.append_value(game.has_evaluations);
+        self.append_final_configuration(&game.final_conf);
+    }
+
+    /// Appends the [`BoardConfiguration`] at the end of a game to the columns.
+    fn append_final_configuration(&mut self, final_configuration: &BoardConfiguration) {
+        let pieces = pieces_left(final_configuration.white_left)
+            .into_iter()
+            .chain(pieces_left(final_configuration.black_left));
+        for (builder, value) in self.pieces_left.iter_mut().zip(pieces) {
+            builder.append_value(value);
+        }
+        for (builder, value) in self.rows.iter_mut().zip(final_configuration.rows) {
+            builder.append_value(value);
+        }
+    }
+
+    /// Number of games in the columns.
+    fn len(&self) -> usize {
+        self.game_id.len()
+    }
+
+    /// Takes the columns as a [`RecordBatch`], leaving them empty.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the columns don't follow the schema.
+    fn finish(&mut self, schema: &SchemaRef) -> Result<RecordBatch> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(self.game_id.finish()),
+            Arc::new(self.event.finish()),
+            Arc::new(self.kind.finish()),
+            Arc::new(self.tournament.finish()),
+            Arc::new(self.white.finish()),
+            Arc::new(self.white_elo.finish()),
+            Arc::new(self.white_title.finish()),
+            Arc::new(self.black.finish()),
+            Arc::new(self.black_elo.finish()),
+            Arc::new(self.black_title.finish()),
+            Arc::new(self.start_time.finish()),
+            Arc::new(self.increment.finish()),
+            Arc::new(self.result.finish()),
+            Arc::new(self.termination.finish()),
+            Arc::new(self.datetime.finish()),
+            Arc::new(self.opening.finish()),
+            Arc::new(self.eco.finish()),
+            Arc::new(self.has_clock.finish()),
+            Arc::new(self.has_evaluations.finish()),
+        ];
+        for builder in &mut self.pieces_left {
+            columns.push(Arc::new(builder.finish()));
+        }
+        for builder in &mut self.rows {
+            columns.push(Arc::new(builder.finish()));
+        }
+        Ok(RecordBatch::try_new(schema.clone(), columns)?)
+    }
+}
+
+/// Builders of the columns of a batch of the Move files.
+#[derive(Debug, Default)]
+struct MoveColumns {
+    game_id: UInt64Builder,
+    num: UInt16Builder,
+    is_white: BooleanBuilder,
+    moved_piece: UInt8Builder,
+    from_row: UInt8Builder,
+    from_column: UInt8Builder,
+    captured_piece: UInt8Builder,
+    to_row: UInt8Builder,
+    to_column: UInt8Builder,
+    promoted_piece: UInt8Builder,
+    is_check: BooleanBuilder,
+    is_mate: BooleanBuilder,
+    nag: UInt8Builder,
+    eval_float: Float32Builder,
+    eval_mate: Int8Builder,
+    clock: Time32SecondBuilder,
+}
+
+impl MoveColumns {
+    /// Appends a [`Move`] to the columns, decoding its descriptor into its fields.
+    fn append(&mut self, game_id: u64, r#move: &Move) {
+        let descriptor = r#move.descriptor.to_u32();
+        let bits = |shift: u32| ((descriptor >> shift) & 0x7) as u8;
+        self.game_id.append_value(game_id);
+        self.num.append_value(r#move.num as u16);
+        self.is_white.append_value(descriptor >> 31 == 1);
+        self.moved_piece.append_value(bits(28));
+        self.from_row.append_value(bits(25));
+        self.from_column.append_value(bits(22));
+        self.captured_piece.append_value(bits(19));
+        self.to_row.append_value(bits(16));
+        self.to_column.append_value(bits(13));
+        self.promoted_piece.append_value(bits(10));
+        self.is_check.append_value((descriptor >> 9) & 1 == 1);
+        self.is_mate.append_value((descriptor >> 8) & 1 == 1);
+        self.nag.append_value((descriptor & 0xFF) as u8);
+        match r#move.eval {
+            Some(Eval::Numeric(num)) => {
+                self.eval_float.append_value(num);
+                self.eval_mate.append_null();
+            }
+            Some(Eval::Checkmate(checkmate)) => {
+                self.eval_float.append_null();
+                self.eval_mate.append_value(checkmate);
+            }
+            None => {
+                self.eval_float.append_null();
+                self.eval_mate.append_null();
+            }
+        }
+        self.clock
+            .append_option(r#move.clk.map(|clk| clk.0.seconds() as i32));
+    }
+
+    /// Number of moves in the columns.
+    fn len(&self) -> usize {
+        self.game_id.len()
+    }
+
+    /// Takes the columns as a [`RecordBatch`], leaving them empty.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the columns don't follow the schema.
+    fn finish(&mut self, schema: &SchemaRef) -> Result<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(self.game_id.finish()),
+                Arc::new(self.num.finish()),
+                Arc::new(self.is_white.finish()),
+                Arc::new(self.moved_piece.finish()),
+                Arc::new(self.from_row.finish()),
+                Arc::new(self.from_column.finish()),
+                Arc::new(self.captured_piece.finish()),
+                Arc::new(self.to_row.finish()),
+                Arc::new(self.to_column.finish()),
+                Arc::new(self.promoted_piece.finish()),
+                Arc::new(self.is_check.finish()),
+                Arc::new(self.is_mate.finish()),
+                Arc::new(self.nag.finish()),
+                Arc::new(self.eval_float.finish()),
+                Arc::new(self.eval_mate.finish()),
+                Arc::new(self.clock.finish()),
+            ],
+        )?)
+    }
+}
+
+/// The files of a month and the batches of its rows yet to be written.
+struct Partition {
+    /// Writer of the Game file.
+    games: ArrowWriter<File>,
+    /// Writer of the Move file.
+    moves: ArrowWriter<File>,
+    /// Games yet to be written.
+    game_columns: GameColumns,
+    /// Moves yet to be written.
+    move_columns: MoveColumns,
+}
+
+impl std::fmt::Debug for Partition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Partition")
+            .field("games", &self.game_columns.len())
+            .field("moves", &self.move_columns.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An exporter of the games and moves to a directory of Parquet files partitioned by month.
+#[derive(Debug)]
+pub struct ParquetExport {
+    /// Directory the partitions are written to.
+    dir: PathBuf,
+    /// Schema of the Game files.
+    game_schema: SchemaRef,
+    /// Schema of the Move files.
+    move_schema: SchemaRef,
+    /// Partitions opened so far by their month.
+    partitions: HashMap<(i32, u8), Partition>,
+    /// ID of the last game written.
+    game_id: u64,
+    /// Number of moves written.
+    moves: u64,
+}
+
+impl ParquetExport {
+    /// Creates the directory, if it doesn't exist, where the partitions will be written to.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the directory could not be created.
+    pub fn create<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        trace!("ParquetExport create function.");
+        let dir = dir.as_ref();
+        info!("Creating the Parquet partitions in {dir:?}.");
+        create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            game_schema: game_schema(),
+            move_schema: move_schema(),
+            partitions: HashMap::new(),
+            game_id: 0,
+            moves: 0,
+        })
+    }
+
+    /// Creates the file of a table for the partition of a month.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the file could not be created.
+    fn create_file(
+        &self,
+        table: &str,
+        (year, month): (i32, u8),
+        schema: &SchemaRef,
+    ) -> Result<ArrowWriter<File>> {
+        let dir = self
+            .dir
+            .join(table)
+            .join(format!("month={year:04}-{month:02}"));
+        create_dir_all(&dir)?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .set_max_row_group_size(ROW_GROUP_SIZE)
+            .build();
+        ArrowWriter::try_new(
+            File::create(dir.join("part-0.parquet"))?,
+            schema.clone(),
+            Some(properties),
+        )
+    }
+
+    /// Gets the partition of a month, creating its files if it is new.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the files could not be created.
+    fn partition(&mut self, month: (i32, u8)) -> Result<&mut Partition> {
+        if !self.partitions.contains_key(&month) {
+            info!("Creating the partition of {:04}-{:02}.", month.0, month.1);
+            let partition = Partition {
+                games: self.create_file(GAME_DIR, month, &self.game_schema)?,
+                moves: self.create_file(MOVE_DIR, month, &self.move_schema)?,
+                game_columns: GameColumns::default(),
+                move_columns: MoveColumns::default(),
+            };
+            self.partitions.insert(month, partition);
+        }
+        Ok(self
+            .partitions
+            .get_mut(&month)
+            .expect("The partition was just inserted if it didn't exist."))
+    }
+
+    /// Writes a [`Data`]'s [`Game`] and [`Vec`] of [`Move`]s into the partition of the month it was played in, flushing the batches that are full.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if any of the writings fail.
+    pub fn write_all(&mut self, data: &Data) -> Result<()> {
+        self.game_id += 1;
+        self.moves += data.moves.len() as u64;
+        let game_id = self.game_id;
+        let (game_schema, move_schema) = (self.game_schema.clone(), self.move_schema.clone());
+        let partition = self.partition(data.game.utc_date.0.year_month())?;
+        partition.game_columns.append(game_id, &data.game);
+        for r#move in &data.moves {
+            partition.move_columns.append(game_id, r#move);
+        }
+        if partition.game_columns.len() >= GAME_BATCH {
+            partition
+                .games
+                .write(&partition.game_columns.finish(&game_schema)?)?;
+        }
+        if partition.move_columns.len() >= MOVE_BATCH {
+            partition
+                .moves
+                .write(&partition.move_columns.finish(&move_schema)?)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining batches and closes every file.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if any of the writings fail.
+    pub fn close(&mut self) -> Result<()> {
+        trace!("ParquetExport close function.");
+        for (_, mut partition) in self.partitions.drain() {
+            partition
+                .games
+                .write(&partition.game_columns.finish(&self.game_schema)?)?;
+            partition
+                .moves
+                .write(&partition.move_columns.finish(&self.move_schema)?)?;
+            partition.games.close()?;
+            partition.moves.close()?;
+        }
+        info!(
+            "Exported {} games and {} moves to Parquet.",
+            self.game_id, self.moves
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{File, remove_dir_all};
+
+    use lichess::data::Data;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use pretty_assertions::assert_eq;
+
+    use super::{GAME_DIR, MOVE_DIR, ParquetExport};
+
+    /// Exports a game with two moves and reads the row counts of its partitions back.
+    #[test]
+    fn parquet_export_test() {
+        let dir = std::env::temp_dir().join("lichess-parquet-test");
+        let mut export = ParquetExport::create(&dir).unwrap();
+        let mut data = Data::default();
+        data.moves.push(data.r#move.clone());
+        data.moves.push(data.r#move.clone());
+        export.write_all(&data).unwrap();
+        export.close().unwrap();
+
+        let (year, month) = data.game.utc_date.0.year_month();
+        let partition = format!("month={year:04}-{month:02}");
+        let rows = |table: &str| {
+            SerializedFileReader::new(
+                File::open(dir.join(table).join(&partition).join("part-0.parquet")).unwrap(),
+            )
+            .unwrap()
+            .metadata()
+            .file_metadata()
+            .num_rows()
+        };
+        assert_eq!(rows(GAME_DIR), 1);
+        assert_eq!(rows(MOVE_DIR), 2);
+        remove_dir_all(dir).unwrap();
+    }
+}
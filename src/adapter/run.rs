@@ -0,0 +1,38 @@
+//! Module of the record of a run of the program, which is kept in the IngestionRun table to audit how the data of the database was loaded.
+
+/// A run of the program that inserted games into the database, with its counts of games per outcome.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestionRun {
+    /// Version of the program.
+    pub version: String,
+    /// Cargo features the program was compiled with, separated by commas.
+    pub features: String,
+    /// Command line arguments the program was called with, separated by spaces.
+    pub arguments: String,
+    /// Seed of the random sample, if only a sample of the file was inserted.
+    pub sample_seed: Option<String>,
+    /// Size of the random sample, if only a sample of the file was inserted.
+    pub sample_size: Option<u64>,
+    /// Name of the file the games were read from, without its directory, so that its games can be purged.
+    pub source_file: Option<String>,
+    /// Number of games read from the file.
+    pub games_parsed: u64,
+    /// Number of games inserted into the database.
+    pub games_inserted: u64,
+    /// Number of games rejected because of their errors, and thus not inserted.
+    pub games_rejected: u64,
+    /// Number of games with errors in their data, whether they were inserted or not. The games that failed to be inserted are the ones neither inserted nor rejected.
+    pub games_with_errors: u64,
+}
+
+impl IngestionRun {
+    /// Creates the record of a run of this version of the program, with no games yet.
+    pub fn new(features: &[&str], arguments: String) -> Self {
+        Self {
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            features: features.join(","),
+            arguments,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Diesel schema of the lichess database, matching the tables created by the `create-*.sql` scripts.
+
+diesel::table! {
+    /// The FinalConfiguration table.
+    #[sql_name = "FinalConfiguration"]
+    final_configuration (fc_id) {
+        #[sql_name = "FCId"]
+        fc_id -> Integer,
+        #[sql_name = "EndBlackPieces"]
+        end_black_pieces -> Unsigned<SmallInt>,
+        #[sql_name = "EndWhitePieces"]
+        end_white_pieces -> Unsigned<SmallInt>,
+        #[sql_name = "ARow"]
+        a_row -> Unsigned<Integer>,
+        #[sql_name = "BRow"]
+        b_row -> Unsigned<Integer>,
+        #[sql_name = "CRow"]
+        c_row -> Unsigned<Integer>,
+        #[sql_name = "DRow"]
+        d_row -> Unsigned<Integer>,
+        #[sql_name = "ERow"]
+        e_row -> Unsigned<Integer>,
+        #[sql_name = "FRow"]
+        f_row -> Unsigned<Integer>,
+        #[sql_name = "GRow"]
+        g_row -> Unsigned<Integer>,
+        #[sql_name = "HRow"]
+        h_row -> Unsigned<Integer>,
+    }
+}
+
+diesel::table! {
+    /// The Game table.
+    #[sql_name = "Game"]
+    game (game_id) {
+        #[sql_name = "GameId"]
+        game_id -> Integer,
+        #[sql_name = "RuleSetId"]
+        ruleset_id -> Integer,
+        #[sql_name = "OpeningId"]
+        opening_id -> Nullable<Integer>,
+        #[sql_name = "FCId"]
+        fc_id -> Integer,
+        #[sql_name = "White"]
+        white -> Nullable<Integer>,
+        #[sql_name = "WhiteElo"]
+        white_elo -> Nullable<Unsigned<SmallInt>>,
+        #[sql_name = "WhiteTitle"]
+        white_title -> Nullable<Text>,
+        #[sql_name = "Black"]
+        black -> Nullable<Integer>,
+        #[sql_name = "BlackElo"]
+        black_elo -> Nullable<Unsigned<SmallInt>>,
+        #[sql_name = "BlackTitle"]
+        black_title -> Nullable<Text>,
+        #[sql_name = "StartTime"]
+        start_time -> Nullable<Unsigned<SmallInt>>,
+        #[sql_name = "Increment"]
+        increment -> Nullable<Unsigned<TinyInt>>,
+        #[sql_name = "Result"]
+        result -> Nullable<Text>,
+        #[sql_name = "Termination"]
+        termination -> Nullable<Text>,
+        #[sql_name = "DateTime"]
+        datetime -> Nullable<Datetime>,
+        #[sql_name = "HasClock"]
+        has_clock -> Nullable<Bool>,
+        #[sql_name = "HasEvaluations"]
+        has_evaluations -> Nullable<Bool>,
+        #[sql_name = "RunId"]
+        run_id -> Nullable<Integer>,
+        #[sql_name = "Moves"]
+        moves -> Nullable<Blob>,
+    }
+}
+
+diesel::table! {
+    /// The IngestionRun table.
+    #[sql_name = "IngestionRun"]
+    ingestion_run (run_id) {
+        #[sql_name = "RunId"]
+        run_id -> Integer,
+        #[sql_name = "Version"]
+        version -> Text,
+        #[sql_name = "Features"]
+        features -> Text,
+        #[sql_name = "Arguments"]
+        arguments -> Text,
+        #[sql_name = "SampleSeed"]
+        sample_seed -> Nullable<Text>,
+        #[sql_name = "SampleSize"]
+        sample_size -> Nullable<Unsigned<Integer>>,
+        #[sql_name = "GamesParsed"]
+        games_parsed -> Unsigned<Integer>,
+        #[sql_name = "GamesInserted"]
+        games_inserted -> Unsigned<Integer>,
+        #[sql_name = "GamesRejected"]
+        games_rejected -> Unsigned<Integer>,
+        #[sql_name = "GamesWithErrors"]
+        games_with_errors -> Unsigned<Integer>,
+        #[sql_name = "StartedAt"]
+        started_at -> Datetime,
+        #[sql_name = "FinishedAt"]
+        finished_at -> Nullable<Datetime>,
+        #[sql_name = "SourceFile"]
+        source_file -> Nullable<Text>,
+        #[sql_name = "PurgedAt"]
+        purged_at -> Nullable<Datetime>,
+    }
+}
+
+diesel::table! {
+    /// The Move table.
+    #[sql_name = "Move"]
+    r#move (game_id, num) {
+        #[sql_name = "GameId"]
+        game_id -> Integer,
+        #[sql_name = "Num"]
+        num -> Unsigned<SmallInt>,
+        #[sql_name = "Descriptor"]
+        descriptor -> Unsigned<Integer>,
+        #[sql_name = "EvalFloat"]
+        eval_float -> Nullable<Float>,
+        #[sql_name = "EvalInt"]
+        eval_int -> Nullable<TinyInt>,
+        #[sql_name = "Clock"]
+        clock -> Nullable<Time>,
+    }
+}
+
+diesel::table! {
+    /// The Opening table.
+    #[sql_name = "Opening"]
+    opening (opening_id) {
+        #[sql_name = "OpeningId"]
+        opening_id -> Integer,
+        #[sql_name = "Name"]
+        name -> Text,
+        #[sql_name = "EcoLetter"]
+        eco_letter -> Text,
+        #[sql_name = "EcoNumber"]
+        eco_number -> Unsigned<TinyInt>,
+    }
+}
+
+diesel::table! {
+    /// The Player table.
+    #[sql_name = "Player"]
+    player (player_id) {
+        #[sql_name = "PlayerId"]
+        player_id -> Integer,
+        #[sql_name = "Name"]
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    /// The RuleSet table.
+    #[sql_name = "RuleSet"]
+    ruleset (ruleset_id) {
+        #[sql_name = "RuleSetId"]
+        ruleset_id -> Integer,
+        #[sql_name = "Name"]
+        name -> Text,
+        #[sql_name = "URLId"]
+        url_id -> Text,
+        #[sql_name = "Kind"]
+        kind -> Text,
+    }
+}
+
+diesel::table! {
+    /// The SchemaVersion table.
+    #[sql_name = "SchemaVersion"]
+    schema_version (version) {
+        #[sql_name = "Version"]
+        version -> Unsigned<Integer>,
+        #[sql_name = "AppliedAt"]
+        applied_at -> Datetime,
+    }
+}
+
+diesel::joinable!(game -> final_configuration (fc_id));
+diesel::joinable!(game -> ingestion_run (run_id));
+diesel::joinable!(game -> opening (opening_id));
+diesel::joinable!(game -> ruleset (ruleset_id));
+diesel::joinable!(r#move -> game (game_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    final_configuration,
+    game,
+    ingestion_run,
+    r#move,
+    opening,
+    player,
+    ruleset,
+);
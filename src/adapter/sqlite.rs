@@ -0,0 +1,764 @@
+//! Module of the SQLite adapter, which keeps the whole database in one file without a server, for single machines and tests.
+
+use log::{info, trace, warn};
+use rusqlite::{Connection as Conn, Error, ErrorCode, ffi, named_params, types::ValueRef};
+
+use lichess::{
+    attributes::{BoardConfiguration, Eco, Eval, Opening, Player, RuleSet, datetime::Datetime},
+    data::{Data, Game, Move, packed::pack},
+};
+
+use super::{
+    Connection, DatabaseAdapter, IdCaches, IngestionRun, MoveStorage, Names, SCHEMA_VERSION, Table,
+    variant,
+};
+
+/// Whether the error is caused by the violation of a unique constraint, meaning the value already existed.
+fn is_duplicate(error: &Error) -> bool {
+    matches!(error, Error::SqliteFailure(e, _) if e.code == ErrorCode::ConstraintViolation && e.extended_code == ffi::SQLITE_CONSTRAINT_UNIQUE)
+}
+
+/// Appends a description of the value that was being inserted to the message of the error.
+fn with_context(error: Error, context: &str) -> Error {
+    match error {
+        Error::SqliteFailure(e, message) => {
+            let mut message = message.unwrap_or_else(|| e.to_string());
+            message.push_str(" (");
+            message.push_str(context);
+            message.push(')');
+            Error::SqliteFailure(e, Some(message))
+        }
+        e => e,
+    }
+}
+
+/// Migrations of the schema, the one at index `i` upgrading it from version `i` to `i + 1`.
+const MIGRATIONS: [&str; SCHEMA_VERSION as usize] = [
+    include_str!("../sql/sqlite/migrations/001-schema-version.sql"),
+    include_str!("../sql/sqlite/migrations/002-ingestion-run.sql"),
+    include_str!("../sql/sqlite/migrations/003-run-source.sql"),
+    include_str!("../sql/sqlite/migrations/004-packed-moves.sql"),
+    include_str!("../sql/sqlite/migrations/005-rollups.sql"),
+];
+
+impl DatabaseAdapter for Connection {
+    type Error = Error;
+
+    fn new(db_url: &str, names: Names) -> Result<Self, Self::Error> {
+        trace!("Connection new function.");
+        info!("Connecting to the database.");
+        let conn = Conn::open(db_url.strip_prefix("sqlite://").unwrap_or(db_url))?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        Ok(Self {
+            conn,
+            cache: IdCaches::default(),
+            names,
+            run_id: None,
+            move_storage: MoveStorage::default(),
+        })
+    }
+
+    fn create_db(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_db function.");
+        info!("Creating the database.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-database.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_final_configuration(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_final_configuration function.");
+        info!("Creating FinalConfiguration table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-finalconf.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_game(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_game function.");
+        info!("Creating Game table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-game.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_move(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_move function.");
+        info!("Creating Move table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-move.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_opening(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_opening function.");
+        info!("Creating Opening table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-opening.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_player(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_player function.");
+        info!("Creating Player table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-player.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_ruleset(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_ruleset function.");
+        info!("Creating RuleSet table.");
+        self.conn.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/create-ruleset.sql")),
+        )?;
+        Ok(self)
+    }
+
+    fn create_views(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_views function.");
+        info!("Creating views.");
+        self.conn
+            .execute_batch(&self.names.sql(include_str!("../sql/view-descriptor.sql")))?;
+        self.conn
+            .execute_batch(&self.names.sql(include_str!("../sql/view-finalboard.sql")))?;
+        self.conn
+            .execute_batch(&self.names.sql(include_str!("../sql/view-piecesleft.sql")))?;
+        Ok(self)
+    }
+
+    fn create_full_database(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection create_full_database function.");
+        info!("Creating full database.");
+        self.create_db()?
+            .create_final_configuration()?
+            .create_opening()?
+            .create_player()?
+            .create_ruleset()?
+            .create_game()?
+            .create_move()?
+            .create_views()?
+            .migrate()?;
+        info!("Database created correctly.");
+        Ok(self)
+    }
+
+    fn prewarm_cache(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection prewarm_cache function.");
+        info!("Loading the existing IDs into the cache.");
+        {
+            let mut statement = self
+                .conn
+                .prepare(&self.names.sql(include_str!("../sql/select-players.sql")))?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                self.cache.players.insert(row.get(1)?, row.get(0)?);
+            }
+        }
+        {
+            let mut statement = self
+                .conn
+                .prepare(&self.names.sql(include_str!("../sql/select-openings.sql")))?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                self.cache.openings.insert(row.get(1)?, row.get(0)?);
+            }
+        }
+        {
+            let mut statement = self
+                .conn
+                .prepare(&self.names.sql(include_str!("../sql/select-rulesets.sql")))?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                self.cache
+                    .rulesets
+                    .insert((row.get(1)?, row.get(2)?), row.get(0)?);
+            }
+        }
+        {
+            let mut statement = self
+                .conn
+                .prepare(&self.names.sql(include_str!("../sql/select-finalconfs.sql")))?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                let mut final_configuration = BoardConfiguration {
+                    black_left: row.get(1)?,
+                    white_left: row.get(2)?,
+                    ..Default::default()
+                };
+                for (i, file) in final_configuration.rows.iter_mut().enumerate() {
+                    *file = row.get(i + 3)?;
+                }
+                self.cache
+                    .final_configurations
+                    .insert(final_configuration, row.get(0)?);
+            }
+        }
+        self.cache.log_sizes();
+        Ok(self)
+    }
+
+    fn defer_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection defer_constraints function.");
+        info!("Disabling the foreign keys, since SQLite can't drop them.");
+        self.conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+        Ok(self)
+    }
+
+    fn constraint_violations(&mut self) -> Result<Vec<(String, u64)>, Self::Error> {
+        let mut statement = self
+            .conn
+            .prepare(&self.names.sql(include_str!("../sql/select-violations.sql")))?;
+        statement
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)))?
+            .collect()
+    }
+
+    fn add_constraints(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection add_constraints function.");
+        self.conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        Ok(self)
+    }
+
+    fn schema_version(&mut self) -> Result<u32, Self::Error> {
+        if self.conn.query_row(
+            include_str!("../sql/sqlite/select-schema-version-table.sql"),
+            [self.names.table("SchemaVersion")],
+            |row| row.get::<_, u32>(0),
+        )? == 0
+        {
+            return Ok(0);
+        }
+        self.conn.query_row(
+            &self
+                .names
+                .sql(include_str!("../sql/select-schema-version.sql")),
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    fn apply_migration(&mut self, version: u32) -> Result<&mut Self, Self::Error> {
+        trace!("Connection apply_migration function.");
+        self.conn.execute_batch("BEGIN;")?;
+        let result = self
+            .conn
+            .execute_batch(&self.names.sql(MIGRATIONS[version as usize - 1]))
+            .and_then(|()| {
+                self.conn
+                    .execute(
+                        &self
+                            .names
+                            .sql(include_str!("../sql/insert-schema-version.sql")),
+                        named_params! { ":version": version },
+                    )
+                    .map(|_| ())
+            });
+        match result {
+            Ok(()) => {
+                self.conn.execute_batch("COMMIT;")?;
+                Ok(self)
+            }
+            Err(e) => {
+                if let Err(rollback) = self.conn.execute_batch("ROLLBACK;") {
+                    warn!("Rollback error: {rollback}");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn initialize_database(db_url: &str, names: Names, rebuild: bool) -> Result<Self, Self::Error> {
+        trace!("Connection initialize_database function.");
+        info!("Initializing connection.");
+        let mut connection = Self::new(db_url, names)?;
+        if rebuild {
+            info!("Rebuilding database.");
+        } else if connection.conn.query_row(
+            include_str!("../sql/sqlite/select-database.sql"),
+            [connection.names.table("Game")],
+            |row| row.get::<_, u32>(0),
+        )? != 0
+        {
+            info!("Database already exists, proceeding.");
+            return Ok(connection);
+        } else {
+            info!("Database doesn't exist, creating.");
+        }
+        connection.create_full_database()?;
+        Ok(connection)
+    }
+
+    fn start_run(&mut self, run: &IngestionRun) -> Result<u64, Self::Error> {
+        trace!("Connection start_run function.");
+        self.conn.execute(
+            &self.names.sql(include_str!("../sql/insert-run.sql")),
+            named_params! {
+                ":version": run.version,
+                ":features": run.features,
+                ":arguments": run.arguments,
+                ":sample_seed": run.sample_seed,
+                ":sample_size": run.sample_size.map(|size| size as i64),
+                ":source_file": run.source_file,
+            },
+        )?;
+        let run_id = self.conn.last_insert_rowid() as u64;
+        self.run_id = Some(run_id);
+        Ok(run_id)
+    }
+
+    fn finish_run(&mut self, run: &IngestionRun) -> Result<&mut Self, Self::Error> {
+        trace!("Connection finish_run function.");
+        if let Some(run_id) = self.run_id {
+            self.conn.execute(
+                &self.names.sql(include_str!("../sql/update-run.sql")),
+                named_params! {
+                    ":run_id": run_id as i64,
+                    ":games_parsed": run.games_parsed as i64,
+                    ":games_inserted": run.games_inserted as i64,
+                    ":games_rejected": run.games_rejected as i64,
+                    ":games_with_errors": run.games_with_errors as i64,
+                },
+            )?;
+        }
+        Ok(self)
+    }
+
+    fn source_runs(&mut self, source_file: &str) -> Result<Vec<u64>, Self::Error> {
+        let mut statement = self.conn.prepare(
+            &self
+                .names
+                .sql(include_str!("../sql/select-source-runs.sql")),
+        )?;
+        statement
+            .query_map(named_params! { ":source_file": source_file }, |row| {
+                Ok(row.get::<_, i64>(0)? as u64)
+            })?
+            .collect()
+    }
+
+    fn purge_run(&mut self, run_id: u64) -> Result<u64, Self::Error> {
+        trace!("Connection purge_run function.");
+        let transaction = self.conn.transaction()?;
+        let params = named_params! { ":run_id": run_id as i64 };
+        transaction.execute(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/subtract-opening-stats.sql")),
+            params,
+        )?;
+        transaction.execute(
+            &self
+                .names
+                .sql(include_str!("../sql/sqlite/subtract-endgame-stats.sql")),
+            params,
+        )?;
+        transaction.execute_batch(
+            &self
+                .names
+                .sql(include_str!("../sql/delete-empty-stats.sql")),
+        )?;
+        transaction.execute(
+            &self.names.sql(include_str!("../sql/delete-run-moves.sql")),
+            params,
+        )?;
+        let games = transaction.execute(
+            &self.names.sql(include_str!("../sql/delete-run-games.sql")),
+            params,
+        )?;
+        transaction.execute(
+            &self.names.sql(include_str!("../sql/purge-run.sql")),
+            params,
+        )?;
+        transaction.commit()?;
+        Ok(games as u64)
+    }
+
+    fn delete_orphans(&mut self) -> Result<&mut Self, Self::Error> {
+        trace!("Connection delete_orphans function.");
+        self.conn
+            .execute_batch(&self.names.sql(include_str!("../sql/delete-orphans.sql")))?;
+        Ok(self)
+    }
+
+    fn select_table(&mut self, query: &str) -> Result<Table, Self::Error> {
+        trace!("Connection select_table function.");
+        let mut statement = self.conn.prepare(&self.names.sql(query))?;
+        let columns = statement
+            .column_names()
+            .into_iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let rows = statement
+            .query_map([], |row| {
+                (0..columns.len())
+                    .map(|index| {
+                        Ok(match row.get_ref(index)? {
+                            ValueRef::Null => None,
+                            ValueRef::Integer(integer) => Some(integer.to_string()),
+                            ValueRef::Real(real) => Some(real.to_string()),
+                            ValueRef::Text(text) | ValueRef::Blob(text) => {
+                                Some(String::from_utf8_lossy(text).into_owned())
+                            }
+                        })
+                    })
+                    .collect()
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(Table { columns, rows })
+    }
+
+    fn insert_final_configuration(
+        &mut self,
+        final_configuration: &BoardConfiguration,
+    ) -> Result<u64, Self::Error> {
+        let [a_row, b_row, c_row, d_row, e_row, f_row, g_row, h_row] = final_configuration.rows;
+        let params = named_params! {
+            ":black_pieces": final_configuration.black_left,
+            ":white_pieces": final_configuration.white_left,
+            ":a_row": a_row,
+            ":b_row": b_row,
+            ":c_row": c_row,
+            ":d_row": d_row,
+            ":e_row": e_row,
+            ":f_row": f_row,
+            ":g_row": g_row,
+            ":h_row": h_row,
+        };
+        match self
+            .conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-finalconf.sql")))?
+            .execute(params)
+        {
+            Ok(_) => return Ok(self.conn.last_insert_rowid() as u64),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(with_context(
+                    e,
+                    &format!("FinalConfiguration: {final_configuration:?}"),
+                ));
+            }
+            Err(_) => (),
+        }
+        self.conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/select-finalconf.sql")))?
+            .query_row(params, |row| row.get(0))
+    }
+
+    fn insert_opening(&mut self, opening: &Opening, eco: Eco) -> Result<u64, Self::Error> {
+        match self
+            .conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-opening.sql")))?
+            .execute(named_params! {
+                ":name": opening.0,
+                ":eco_letter": variant(eco.0),
+                ":eco_number": eco.1.get(),
+            }) {
+            Ok(_) => return Ok(self.conn.last_insert_rowid() as u64),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(with_context(
+                    e,
+                    &format!(
+                        "Opening: {} {}{:02}",
+                        opening.0,
+                        eco.0.as_char(),
+                        eco.1.get()
+                    ),
+                ));
+            }
+            Err(_) => (),
+        }
+        self.conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/select-opening.sql")))?
+            .query_row(named_params! {":name": opening.0}, |row| row.get(0))
+    }
+
+    fn insert_player(&mut self, player: &Player) -> Result<u64, Self::Error> {
+        let params = named_params! {":name": player.0};
+        match self
+            .conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-player.sql")))?
+            .execute(params)
+        {
+            Ok(_) => return Ok(self.conn.last_insert_rowid() as u64),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(with_context(e, &format!("Player: {}", player.0)));
+            }
+            Err(_) => (),
+        }
+        self.conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/select-player.sql")))?
+            .query_row(params, |row| row.get(0))
+    }
+
+    fn insert_ruleset(&mut self, ruleset: &RuleSet) -> Result<u64, Self::Error> {
+        match self
+            .conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-ruleset.sql")))?
+            .execute(named_params! {
+                ":name": ruleset.name,
+                ":url_id": ruleset.url,
+                ":kind": variant(ruleset.kind),
+            }) {
+            Ok(_) => return Ok(self.conn.last_insert_rowid() as u64),
+            Err(e) if !is_duplicate(&e) => {
+                return Err(with_context(
+                    e,
+                    &format!("RuleSet: {} - {}", ruleset.name, ruleset.kind.as_str()),
+                ));
+            }
+            Err(_) => (),
+        }
+        self.conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/select-ruleset.sql")))?
+            .query_row(
+                named_params! {":name": ruleset.name, ":url_id": ruleset.url},
+                |row| row.get(0),
+            )
+    }
+
+    fn insert_game(
+        &mut self,
+        game: &Game,
+        ruleset_id: u64,
+        opening_id: Option<u64>,
+        fc_id: u64,
+        white_id: Option<u64>,
+        black_id: Option<u64>,
+    ) -> Result<u64, Self::Error> {
+        let (start_time, increment) = game.time_control.0.unzip();
+        self.conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-game.sql")))?
+            .execute(named_params! {
+                ":ruleset_id": ruleset_id as i64,
+                ":opening_id": opening_id.map(|id| id as i64),
+                ":fc_id": fc_id as i64,
+                ":white": white_id.map(|id| id as i64),
+                ":white_elo": game.white_elo.0,
+                ":white_title": game.white_title.map(variant),
+                ":black": black_id.map(|id| id as i64),
+                ":black_elo": game.black_elo.0,
+                ":black_title": game.black_title.map(variant),
+                ":start_time": start_time,
+                ":increment": increment,
+                ":result": variant(game.result),
+                ":termination": variant(game.termination),
+                ":datetime": Datetime::new(game.utc_date.0, game.utc_time.0).0,
+                ":has_clock": game.has_clock,
+                ":has_evaluations": game.has_evaluations,
+                ":run_id": self.run_id.map(|id| id as i64),
+            })?;
+        Ok(self.conn.last_insert_rowid() as u64)
+    }
+
+    fn insert_game_data(&mut self, game: &Game) -> Result<u64, Self::Error> {
+        let ruleset_id = self.ruleset_id(&game.ruleset)?;
+        let opening_id = if game.opening.0.is_empty() {
+            None
+        } else {
+            Some(self.opening_id(&game.opening, game.eco)?)
+        };
+        let fc_id = self.final_configuration_id(&game.final_conf)?;
+        let white_id = if game.white.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.white)?)
+        };
+        let black_id = if game.black.0.is_empty() {
+            None
+        } else {
+            Some(self.player_id(&game.black)?)
+        };
+        self.insert_game(game, ruleset_id, opening_id, fc_id, white_id, black_id)
+    }
+
+    fn insert_move(&mut self, r#move: &Move, game_id: u64) -> Result<&mut Self, Self::Error> {
+        self.insert_moves(std::slice::from_ref(r#move), game_id)
+    }
+
+    fn insert_moves(&mut self, moves: &[Move], game_id: u64) -> Result<&mut Self, Self::Error> {
+        let mut statement = self
+            .conn
+            .prepare_cached(&self.names.sql(include_str!("../sql/insert-move.sql")))?;
+        for r#move in moves {
+            let (eval_float, eval_int) = match r#move.eval {
+                Some(Eval::Numeric(num)) => (Some(num), None),
+                Some(Eval::Checkmate(checkmate)) => (None, Some(checkmate)),
+                None => (None, None),
+            };
+            statement.execute(named_params! {
+                ":game_id": game_id as i64,
+                ":num": r#move.num as i64,
+                ":descriptor": r#move.descriptor.to_u32(),
+                ":eval_float": eval_float,
+                ":eval_int": eval_int,
+                ":clock": r#move.clk.map(|clk| clk.0.0),
+            })?;
+        }
+        drop(statement);
+        Ok(self)
+    }
+
+    fn insert_packed_moves(
+        &mut self,
+        moves: &[Move],
+        game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        self.conn.execute(
+            &self.names.sql(include_str!("../sql/update-game-moves.sql")),
+            named_params! {
+                ":game_id": game_id as i64,
+                ":moves": pack(moves),
+            },
+        )?;
+        Ok(self)
+    }
+
+    fn move_storage(&self) -> MoveStorage {
+        self.move_storage
+    }
+
+    fn update_rollups(
+        &mut self,
+        first_game_id: u64,
+        last_game_id: u64,
+    ) -> Result<&mut Self, Self::Error> {
+        let params = named_params! {
+            ":first_game_id": first_game_id as i64,
+            ":last_game_id": last_game_id as i64,
+        };
+        self.conn
+            .prepare_cached(
+                &self
+                    .names
+                    .sql(include_str!("../sql/sqlite/add-opening-stats.sql")),
+            )?
+            .execute(params)?;
+        self.conn
+            .prepare_cached(
+                &self
+                    .names
+                    .sql(include_str!("../sql/sqlite/add-endgame-stats.sql")),
+            )?
+            .execute(params)?;
+        Ok(self)
+    }
+
+    fn insert_all(&mut self, data: &Data) -> Result<&mut Self, Self::Error> {
+        self.transaction(|connection| {
+            let game_id = connection.insert_game_data(&data.game)?;
+            connection
+                .store_moves(&data.moves, game_id)?
+                .update_rollups(game_id, game_id)?;
+            Ok(())
+        })?;
+        Ok(self)
+    }
+
+    fn insert_batch(&mut self, batch: &[(Game, Vec<Move>)]) -> Result<&mut Self, Self::Error> {
+        self.transaction(|connection| {
+            for (game, moves) in batch {
+                let game_id = connection.insert_game_data(game)?;
+                connection
+                    .store_moves(moves, game_id)?
+                    .update_rollups(game_id, game_id)?;
+            }
+            Ok(())
+        })?;
+        Ok(self)
+    }
+}
+
+impl Connection {
+    /// Runs insertions in a transaction, so that either all of them are kept or none. The IDs cached are cleared if the transaction is rolled back, since they may not exist anymore.
+    ///
+    /// # Errors
+    /// Will return [`Error`] if the insertions or the commit fail.
+    fn transaction<F>(&mut self, insertions: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        self.conn.execute_batch("BEGIN;")?;
+        let result = insertions(self).and_then(|()| self.conn.execute_batch("COMMIT;"));
+        if result.is_err() {
+            self.cache.clear();
+            if let Err(e) = self.conn.execute_batch("ROLLBACK;") {
+                warn!("Rollback error: {e}");
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lichess::{attributes::Player, data::Data};
+    use pretty_assertions::assert_eq;
+
+    use super::{Connection, DatabaseAdapter, Names};
+
+    #[test]
+    fn sqlite_insertion_test() {
+        let mut connection =
+            Connection::initialize_database(":memory:", Names::default(), false).unwrap();
+
+        let player = Player(String::from("DrNykterstein"));
+        let id = connection.insert_player(&player).unwrap();
+        assert_eq!(connection.insert_player(&player).unwrap(), id);
+
+        let mut data = Data::default();
+        data.game.white = player;
+        data.r#move.num = 1;
+        data.moves.push(data.r#move.clone());
+        connection.insert_all(&data).unwrap();
+        connection.insert_all(&data).unwrap();
+        let count = |connection: &Connection, query: &str| {
+            connection
+                .conn
+                .query_row(query, [], |row| row.get::<_, u32>(0))
+                .unwrap()
+        };
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM Game"), 2);
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM Player"), 1);
+        assert_eq!(
+            count(&connection, "SELECT COUNT(*) FROM FinalConfiguration"),
+            1
+        );
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM MoveDescriptor"), 2);
+        assert_eq!(count(&connection, "SELECT SUM(Games) FROM OpeningStats"), 2);
+        assert_eq!(count(&connection, "SELECT SUM(Games) FROM EndgameStats"), 2);
+
+        connection
+            .conn
+            .execute_batch("DROP TABLE EndgameStats;")
+            .unwrap();
+        assert!(connection.insert_all(&data).is_err());
+        assert!(
+            connection
+                .insert_batch(&[(data.game.clone(), data.moves.clone())])
+                .is_err()
+        );
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM Game"), 2);
+        assert_eq!(count(&connection, "SELECT COUNT(*) FROM Move"), 2);
+        assert_eq!(count(&connection, "SELECT SUM(Games) FROM OpeningStats"), 2);
+    }
+}
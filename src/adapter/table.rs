@@ -0,0 +1,134 @@
+//! Module of the result of a query whose columns are only known when it runs, kept as text so that it can be written as CSV, JSON or Markdown.
+
+use std::io::{self, Write};
+
+use lichess::report::write_json_str;
+
+/// The columns and rows selected by a query, where each value is its text or [`None`] if it is NULL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Names of the columns, in the order of the values of each row.
+    pub columns: Vec<String>,
+    /// Values of each row.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break, doubling its quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Whether a value is a number as JSON writes it, which excludes the signs, leading zeros and special values that Rust would parse.
+fn is_json_number(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && !(digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit()))
+        && value.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+impl Table {
+    /// Writes the table as CSV, with a header of the names of the columns and NULL values left empty.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header = self.columns.iter().map(|column| csv_field(column));
+        writeln!(writer, "{}", header.collect::<Vec<_>>().join(","))?;
+        for row in &self.rows {
+            let fields = row
+                .iter()
+                .map(|value| value.as_deref().map(csv_field).unwrap_or_default());
+            writeln!(writer, "{}", fields.collect::<Vec<_>>().join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the table as a JSON array with an object per row, keyed by the names of the columns. Numeric values are written as numbers and NULL values as `null`.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "[")?;
+        for (index, row) in self.rows.iter().enumerate() {
+            write!(writer, "{}{{", if index == 0 { "" } else { "," })?;
+            for (index, (column, value)) in self.columns.iter().zip(row).enumerate() {
+                if index > 0 {
+                    write!(writer, ",")?;
+                }
+                write_json_str(writer, column)?;
+                write!(writer, ":")?;
+                match value {
+                    Some(value) if is_json_number(value) => {
+                        write!(writer, "{value}")?;
+                    }
+                    Some(value) => write_json_str(writer, value)?,
+                    None => write!(writer, "null")?,
+                }
+            }
+            write!(writer, "}}")?;
+        }
+        writeln!(writer, "]")
+    }
+
+    /// Writes the table as a Markdown table, escaping the pipes of the values and leaving NULL values empty.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write_markdown<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let cell = |value: &str| value.replace('|', "\\|");
+        let header = self.columns.iter().map(|column| cell(column));
+        writeln!(writer, "| {} |", header.collect::<Vec<_>>().join(" | "))?;
+        writeln!(writer, "|{}", " --- |".repeat(self.columns.len()))?;
+        for row in &self.rows {
+            let cells = row
+                .iter()
+                .map(|value| value.as_deref().map(cell).unwrap_or_default());
+            writeln!(writer, "| {} |", cells.collect::<Vec<_>>().join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Table;
+
+    /// Tests that the values are quoted or escaped as each format needs.
+    #[test]
+    fn write_table_test() {
+        let table = Table {
+            columns: vec![String::from("opening_family"), String::from("total_games")],
+            rows: vec![
+                vec![
+                    Some(String::from("Queen's Pawn Game")),
+                    Some(String::from("12")),
+                ],
+                vec![Some(String::from("A, \"B\" | C")), None],
+            ],
+        };
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "opening_family,total_games\nQueen's Pawn Game,12\n\"A, \"\"B\"\" | C\",\n"
+        );
+        let mut json = Vec::new();
+        table.write_json(&mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "[{\"opening_family\":\"Queen's Pawn Game\",\"total_games\":12},{\"opening_family\":\"A, \\\"B\\\" | C\",\"total_games\":null}]\n"
+        );
+        let mut markdown = Vec::new();
+        table.write_markdown(&mut markdown).unwrap();
+        assert_eq!(
+            String::from_utf8(markdown).unwrap(),
+            "| opening_family | total_games |\n| --- | --- |\n| Queen's Pawn Game | 12 |\n| A, \"B\" \\| C |  |\n"
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Module of the analysis reports, which are the analysis queries embedded and parameterized, so that they run through the connection to the database and their tables are written as CSV, JSON or Markdown.
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use crate::adapter::Table;
+
+/// Upper bounds of the default buckets of the average Elo of the players of a game, the last one being the lower bound of the highest bucket.
+pub const DEFAULT_ELO_BUCKETS: [u16; 4] = [1400, 1600, 1800, 2200];
+/// Opening families reported by default, which are the most played ones.
+pub const DEFAULT_OPENINGS: [&str; 20] = [
+    "Sicilian Defense",
+    "Queen's Pawn Game",
+    "French Defense",
+    "Scandinavian Defense",
+    "Italian Game",
+    "Caro-Kann Defense",
+    "King's Pawn Game",
+    "Queen's Gambit Declined",
+    "English Opening",
+    "Modern Defense",
+    "Zukertort Opening",
+    "Philidor Defense",
+    "Indian Game",
+    "Russian Game",
+    "Ruy Lopez",
+    "Van't Kruijs Opening",
+    "Bishop's Opening",
+    "Hungarian Opening",
+    "Pirc Defense",
+    "Scotch Game",
+];
+/// Bounds of the buckets of the difference of Elo between the players of a game.
+const DIFFERENCE_BUCKETS: [u16; 5] = [50, 100, 200, 300, 400];
+/// Types of endgame classified by `endgames.sql`, each of which is counted in a column.
+const ENDGAMES: [&str; 5] = [
+    "rook_endings",
+    "queen_endings",
+    "two_bishops_endings",
+    "bishop_knight_endings",
+    "empty_endings",
+];
+/// Average Elo of the players of a game.
+const ELO: &str = "(WhiteElo + BlackElo) / 2";
+/// Difference of Elo between the players of a game, written so that unsigned columns never become negative.
+const DIFFERENCE: &str = "CASE WHEN g.WhiteElo > g.BlackElo THEN g.WhiteElo - g.BlackElo ELSE g.BlackElo - g.WhiteElo END";
+/// Family of an opening, which is its name up to the first colon.
+#[cfg(any(
+    feature = "time-mysql",
+    feature = "chrono-mysql",
+    feature = "time-diesel",
+    feature = "chrono-diesel"
+))]
+const OPENING_FAMILY: &str = "SUBSTRING_INDEX(o.Name, ':', 1)";
+/// Family of an opening, which is its name up to the first colon.
+#[cfg(any(feature = "time-postgres", feature = "chrono-postgres"))]
+const OPENING_FAMILY: &str = "SPLIT_PART(o.Name, ':', 1)";
+/// Family of an opening, which is its name up to the first colon.
+#[cfg(any(feature = "time-sqlite", feature = "chrono-sqlite"))]
+const OPENING_FAMILY: &str = "SUBSTR(o.Name, 1, INSTR(o.Name || ':', ':') - 1)";
+/// Month a game was played in, as `YYYY-MM`.
+#[cfg(any(
+    feature = "time-mysql",
+    feature = "chrono-mysql",
+    feature = "time-diesel",
+    feature = "chrono-diesel"
+))]
+const MONTH: &str = "DATE_FORMAT(g.DateTime, '%Y-%m')";
+/// Month a game was played in, as `YYYY-MM`.
+#[cfg(any(feature = "time-postgres", feature = "chrono-postgres"))]
+const MONTH: &str = "TO_CHAR(g.DateTime, 'YYYY-MM')";
+/// Month a game was played in, as `YYYY-MM`.
+#[cfg(any(feature = "time-sqlite", feature = "chrono-sqlite"))]
+const MONTH: &str = "STRFTIME('%Y-%m', g.DateTime)";
+
+/// An analysis of the games of the database, each of which is a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Analysis {
+    /// Games of each opening family per bucket of average Elo.
+    OpeningElo,
+    /// Games of each opening family per month.
+    OpeningEvolution,
+    /// Games of each type of endgame per bucket of average Elo.
+    EndingsElo,
+    /// Games of each type of endgame per opening family.
+    EndingsOpening,
+    /// Games of each type of endgame per ruleset.
+    EndingsRuleset,
+    /// Wins and draws of the player with the advantage in each type of endgame, per bucket of average Elo.
+    EndingsWinrate,
+    /// Wins of the player with the higher Elo per bucket of difference of Elo.
+    HigherEloWinrate,
+}
+
+impl Analysis {
+    /// Gets the name of the analysis, as it is given in the command line.
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .expect("Every analysis must have a name.")
+            .get_name()
+            .to_owned()
+    }
+
+    /// Gets the template of the query of the analysis, whose placeholders are filled in by [`Parameters::query`].
+    const fn template(self) -> &'static str {
+        match self {
+            Self::OpeningElo => include_str!("sql/reports/opening-elo.sql"),
+            Self::OpeningEvolution => include_str!("sql/reports/opening-evolution.sql"),
+            Self::EndingsElo => include_str!("sql/reports/endings-elo.sql"),
+            Self::EndingsOpening => include_str!("sql/reports/endings-opening.sql"),
+            Self::EndingsRuleset => include_str!("sql/reports/endings-ruleset.sql"),
+            Self::EndingsWinrate => include_str!("sql/reports/endings-winrate.sql"),
+            Self::HigherEloWinrate => include_str!("sql/reports/higher-elo-winrate.sql"),
+        }
+    }
+}
+
+/// The format a report is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Comma separated values, with a header.
+    Csv,
+    /// An array of objects, one per row.
+    Json,
+    /// A Markdown table.
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Gets the extension of the files written in this format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Markdown => "md",
+        }
+    }
+
+    /// Writes a table in this format.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write<W: Write>(self, table: &Table, writer: &mut W) -> io::Result<()> {
+        match self {
+            Self::Csv => table.write_csv(writer),
+            Self::Json => table.write_json(writer),
+            Self::Markdown => table.write_markdown(writer),
+        }
+    }
+}
+
+/// The parameters of the analyses, which restrict the games and group them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    /// First day of the games, as `YYYY-MM-DD`, if they are restricted.
+    pub from: Option<String>,
+    /// Last day of the games, as `YYYY-MM-DD`, if they are restricted.
+    pub to: Option<String>,
+    /// Upper bounds of the buckets of average Elo, in increasing order.
+    pub elo_buckets: Vec<u16>,
+    /// Opening families to report, in the order of their rows.
+    pub openings: Vec<String>,
+}
+
+/// Quotes a value as an SQL string literal, doubling its quotes, and its backslashes with MySQL, where they escape the next character.
+fn literal(value: &str) -> String {
+    #[cfg(any(
+        feature = "time-mysql",
+        feature = "chrono-mysql",
+        feature = "time-diesel",
+        feature = "chrono-diesel"
+    ))]
+    let value = value.replace('\\', "\\\\");
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Labels the buckets delimited by increasing bounds: below the first one, between each pair and above the last one.
+fn bucket_labels(bounds: &[u16]) -> Vec<String> {
+    let mut labels = Vec::with_capacity(bounds.len() + 1);
+    labels.push(format!("Under {}", bounds[0]));
+    labels.extend(
+        bounds
+            .windows(2)
+            .map(|pair| format!("{}-{}", pair[0], pair[1] - 1)),
+    );
+    labels.push(format!("{}+", bounds[bounds.len() - 1]));
+    labels
+}
+
+/// Builds an SQL expression of the label of the bucket an expression falls into.
+fn bucket_case(expression: &str, bounds: &[u16]) -> String {
+    let labels = bucket_labels(bounds);
+    let whens = bounds
+        .iter()
+        .zip(&labels)
+        .map(|(bound, label)| format!("WHEN {expression} < {bound} THEN {}", literal(label)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "CASE {whens} ELSE {} END",
+        literal(&labels[labels.len() - 1])
+    )
+}
+
+/// Builds the SQL columns that count the rows whose expression falls into each bucket, named after the bucket with a prefix.
+fn bucket_columns(expression: &str, bounds: &[u16], prefix: &str) -> String {
+    let mut columns = Vec::with_capacity(bounds.len() + 1);
+    columns.push(format!(
+        "SUM(CASE WHEN {expression} < {} THEN 1 ELSE 0 END) AS {prefix}_under_{}",
+        bounds[0], bounds[0]
+    ));
+    columns.extend(bounds.windows(2).map(|pair| {
+        format!(
+            "SUM(CASE WHEN {expression} >= {} AND {expression} < {} THEN 1 ELSE 0 END) AS {prefix}_{}_{}",
+            pair[0],
+            pair[1],
+            pair[0],
+            pair[1] - 1
+        )
+    }));
+    let last = bounds[bounds.len() - 1];
+    columns.push(format!(
+        "SUM(CASE WHEN {expression} >= {last} THEN 1 ELSE 0 END) AS {prefix}_{last}_up"
+    ));
+    columns.join(", ")
+}
+
+/// Fills the placeholders of a template, written as `{name}`, in one pass, so that the values filled in are never filled in again.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut query = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        query.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(name, _)| *name == &rest[1..end])
+                .map(|(_, value)| (end, value))
+        });
+        if let Some((end, value)) = placeholder {
+            query.push_str(value);
+            rest = &rest[end + 1..];
+        } else {
+            query.push('{');
+            rest = &rest[1..];
+        }
+    }
+    query.push_str(rest);
+    query
+}
+
+impl Parameters {
+    /// Builds the SQL condition that restricts the games to the range of days, if any.
+    fn filter(&self) -> String {
+        let conditions = [
+            self.from
+                .as_ref()
+                .map(|from| format!("g.DateTime >= {}", literal(&format!("{from} 00:00:00")))),
+            self.to
+                .as_ref()
+                .map(|to| format!("g.DateTime <= {}", literal(&format!("{to} 23:59:59")))),
+        ];
+        let conditions = conditions.into_iter().flatten().collect::<Vec<_>>();
+        if conditions.is_empty() {
+            String::from("1 = 1")
+        } else {
+            conditions.join(" AND ")
+        }
+    }
+
+    /// Builds the query of an analysis with these parameters, which still uses the default names of the database and its tables.
+    pub fn query(&self, analysis: Analysis) -> String {
+        let openings = self
+            .openings
+            .iter()
+            .map(|opening| literal(opening))
+            .collect::<Vec<_>>();
+        let opening_order = format!(
+            "CASE {OPENING_FAMILY} {} ELSE {} END",
+            openings
+                .iter()
+                .enumerate()
+                .map(|(index, opening)| format!("WHEN {opening} THEN {index}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            openings.len()
+        );
+        let ending_columns = ENDGAMES
+            .iter()
+            .map(|endgame| {
+                format!("SUM(CASE WHEN EndgameType = '{endgame}' THEN 1 ELSE 0 END) AS {endgame}")
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let template = analysis.template().replace(
+            "{endgames}",
+            include_str!("sql/reports/endgames.sql").trim_end(),
+        );
+        fill(
+            &template,
+            &[
+                ("filter", &self.filter()),
+                ("opening_family", OPENING_FAMILY),
+                ("openings", &openings.join(", ")),
+                ("opening_order", &opening_order),
+                ("month", MONTH),
+                ("elo_bucket", &bucket_case(ELO, &self.elo_buckets)),
+                (
+                    "elo_columns",
+                    &bucket_columns(ELO, &self.elo_buckets, "elo"),
+                ),
+                ("elo", ELO),
+                ("ending_columns", &ending_columns),
+                (
+                    "difference_bucket",
+                    &bucket_case(DIFFERENCE, &DIFFERENCE_BUCKETS),
+                ),
+                ("difference", DIFFERENCE),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{bucket_case, bucket_columns, bucket_labels, fill, literal};
+
+    /// Tests that the buckets cover every value, from below the first bound to above the last one.
+    #[test]
+    fn buckets_test() {
+        assert_eq!(
+            bucket_labels(&[1400, 1600, 2200]),
+            ["Under 1400", "1400-1599", "1600-2199", "2200+"]
+        );
+        assert_eq!(
+            bucket_case("Elo", &[1400, 1600]),
+            "CASE WHEN Elo < 1400 THEN 'Under 1400' WHEN Elo < 1600 THEN '1400-1599' ELSE '1600+' END"
+        );
+        assert_eq!(
+            bucket_columns("Elo", &[1400, 1600], "elo"),
+            "SUM(CASE WHEN Elo < 1400 THEN 1 ELSE 0 END) AS elo_under_1400, SUM(CASE WHEN Elo >= 1400 AND Elo < 1600 THEN 1 ELSE 0 END) AS elo_1400_1599, SUM(CASE WHEN Elo >= 1600 THEN 1 ELSE 0 END) AS elo_1600_up"
+        );
+    }
+
+    /// Tests that the values can't end their literal early and that the placeholders in the values filled in are kept as they are.
+    #[test]
+    fn literal_test() {
+        let backslash = if cfg!(any(
+            feature = "time-mysql",
+            feature = "chrono-mysql",
+            feature = "time-diesel",
+            feature = "chrono-diesel"
+        )) {
+            "\\\\"
+        } else {
+            "\\"
+        };
+        assert_eq!(
+            literal("Foo\\' OR 1=1 -- "),
+            format!("'Foo{backslash}'' OR 1=1 -- '")
+        );
+        assert_eq!(
+            fill(
+                "WHERE Name IN ({openings}) AND {filter} {unknown}",
+                &[("openings", "'{filter}'"), ("filter", "1 = 1")]
+            ),
+            "WHERE Name IN ('{filter}') AND 1 = 1 {unknown}"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Flat CLI arguments of the previous versions of the scrapper, whose behaviour depended on the combination of their flags, kept so that the old invocations and argfiles are translated into their command.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser};
+use clap_verbosity_flag::{InfoLevel, Verbosity};
+
+use super::{
+    CLIArgs, CheckArgs, CheckCommandArgs, CollectArgs, Command, Config, ConnectionArgs, ExportArgs,
+    IngestArgs, InsertArgs, LogFormat, PurgeArgs, SampleArgs, identifier,
+};
+use crate::adapter::{DEFAULT_CACHE_SIZE, DEFAULT_DATABASE_NAME, MoveStorage};
+
+/// Flat CLI arguments of the previous versions.
+#[derive(Parser, Debug)]
+pub struct LegacyArgs {
+    /// PGN or ZSTD compressed PGN file to read data from.
+    #[arg(required_unless_present_any = ["migrate", "purge_run", "purge_file"])]
+    pgn_file: Option<PathBuf>,
+    /// Whether the terminal should remain silent or not.
+    #[arg(short, long)]
+    silent: bool,
+    /// File to log runtime information to.
+    #[arg(short = 'l', long)]
+    log_file: Option<PathBuf>,
+    /// JSON lines file to write the errors found to.
+    #[arg(short, long)]
+    error_report: Option<PathBuf>,
+    /// PGN or ZSTD compressed PGN file to write the games that fail to.
+    #[arg(long)]
+    rejects: Option<PathBuf>,
+    /// Clap verbosity flag.
+    #[command(flatten)]
+    verbose: Verbosity<InfoLevel>,
+    /// Arguments for checking data consistency.
+    #[command(flatten)]
+    consistency: LegacyConsistencyArgs,
+    /// Arguments for interacting with the database.
+    #[command(flatten)]
+    database: LegacyDatabaseArgs,
+    /// Arguments for exporting the data to CSV or Parquet files.
+    #[command(flatten)]
+    export: LegacyExportArgs,
+}
+
+/// Flat arguments for checking data consistency.
+#[derive(Args, Debug)]
+#[group(id = "consistency", multiple = true, requires = "check")]
+struct LegacyConsistencyArgs {
+    /// Whether or not to check for data consistency.
+    #[arg(short, long)]
+    check: bool,
+    /// Whether or not to print data found while checking.
+    #[arg(short, long, conflicts_with = "sample")]
+    print_collect: bool,
+    /// File to write the data found while checking.
+    #[arg(short, long, conflicts_with = "sample")]
+    write_collect: Option<PathBuf>,
+}
+
+/// Flat arguments for connecting to the database.
+#[derive(Args, Debug)]
+#[group(id = "database", multiple = true)]
+struct LegacyDatabaseArgs {
+    /// File to read the environment variables from.
+    #[arg(short, long = "envfile")]
+    db_envfile: Option<PathBuf>,
+    /// Total number of games in this file.
+    #[arg(short = 'n', long, requires = "sample")]
+    games: Option<usize>,
+    /// Number of games of a random sample of the file.
+    #[arg(short = 'm', long = "sample-size")]
+    sample: Option<usize>,
+    /// Name of the database to use.
+    #[arg(long, default_value = DEFAULT_DATABASE_NAME, value_parser = identifier)]
+    db_name: String,
+    /// Prefix of the names of the tables.
+    #[arg(long, default_value = "", value_parser = identifier)]
+    table_prefix: String,
+    /// Whether to rebuild the database.
+    #[arg(short, long)]
+    rebuild: bool,
+    /// Whether to upgrade the schema of the database.
+    #[arg(long, conflicts_with_all = ["rebuild", "sample"])]
+    migrate: bool,
+    /// ID of an ingestion run whose games to delete.
+    #[arg(long, conflicts_with_all = ["rebuild", "sample", "migrate", "purge_file"])]
+    purge_run: Option<u64>,
+    /// Source file whose games to delete.
+    #[arg(long, conflicts_with_all = ["rebuild", "sample", "migrate"])]
+    purge_file: Option<PathBuf>,
+    /// Whether to insert the data into an in-memory database.
+    #[arg(long, conflicts_with_all = ["sample", "rejects", "migrate", "purge_run", "purge_file"])]
+    dry_run: bool,
+    /// Whether or not to continue even with errors.
+    #[arg(short, long, requires = "check")]
+    force_insert: bool,
+    /// Number of games to insert together in one transaction.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..), conflicts_with_all = ["sample", "rejects"])]
+    batch_size: u16,
+    /// Maximum number of IDs to keep in memory in each cache.
+    #[arg(long, default_value_t = DEFAULT_CACHE_SIZE)]
+    cache_size: usize,
+    /// Whether to load the IDs already in the database into the caches.
+    #[arg(long, conflicts_with = "rebuild")]
+    prewarm_cache: bool,
+    /// Whether to defer the constraints of the Game and Move tables.
+    #[arg(long, conflicts_with_all = ["migrate", "purge_run", "purge_file"])]
+    bulk: bool,
+    /// How to store the moves of the games.
+    #[arg(long, value_enum, default_value_t = MoveStorage::Rows, conflicts_with_all = ["migrate", "purge_run", "purge_file"])]
+    move_storage: MoveStorage,
+}
+
+/// Flat arguments for exporting the data to CSV or Parquet files.
+#[derive(Args, Debug)]
+#[group(id = "export", multiple = true)]
+struct LegacyExportArgs {
+    /// Directory to export the data to as CSV files.
+    #[arg(long, conflicts_with_all = ["sample", "rejects"])]
+    export_csv: Option<PathBuf>,
+    /// Whether or not to load the exported CSV files into the database.
+    #[arg(long, requires = "export_csv")]
+    load_csv: bool,
+    /// Directory to export the games and moves to as Parquet files.
+    #[arg(long, conflicts_with_all = ["sample", "rejects", "export_csv"])]
+    export_parquet: Option<PathBuf>,
+}
+
+impl LegacyExportArgs {
+    /// Whether any of the exports was asked for.
+    const fn is_set(&self) -> bool {
+        self.export_csv.is_some() || self.export_parquet.is_some()
+    }
+}
+
+impl LegacyArgs {
+    /// Gets the arguments for connecting to the database and naming its tables.
+    fn connection(&self, database_url: Option<&String>) -> ConnectionArgs {
+        ConnectionArgs {
+            database_url: database_url.cloned(),
+            db_name: self.database.db_name.clone(),
+            table_prefix: self.database.table_prefix.clone(),
+        }
+    }
+
+    /// Gets the arguments for checking the games first.
+    const fn check_args(&self) -> CheckArgs {
+        CheckArgs {
+            check: self.consistency.check,
+            force_insert: self.database.force_insert,
+        }
+    }
+
+    /// Gets the arguments for inserting the games, taking the rejects file.
+    fn insert_args(&mut self, database_url: Option<&String>) -> InsertArgs {
+        InsertArgs {
+            connection: self.connection(database_url),
+            rebuild: self.database.rebuild,
+            rejects: self.rejects.take(),
+            cache_size: self.database.cache_size,
+            prewarm_cache: self.database.prewarm_cache,
+            bulk: self.database.bulk,
+            move_storage: self.database.move_storage,
+        }
+    }
+
+    /// Translates the flat flags into the command they ran, taking the files out of them. The collection of data is kept over an insertion it was combined with, which now is the ingest command.
+    fn command(&mut self, database_url: Option<&String>) -> Command {
+        let pgn_file = self.pgn_file.take().unwrap_or_default();
+        if self.database.migrate {
+            return Command::Migrate(self.connection(database_url));
+        }
+        if self.database.purge_run.is_some() || self.database.purge_file.is_some() {
+            return Command::Purge(PurgeArgs {
+                purge_run: self.database.purge_run,
+                purge_file: self.database.purge_file.take(),
+                connection: self.connection(database_url),
+            });
+        }
+        if let Some(sample) = self.database.sample {
+            return Command::Sample(SampleArgs {
+                pgn_file,
+                sample,
+                games: self.database.games,
+                output: None,
+                consistency: self.check_args(),
+                database: self.insert_args(database_url),
+            });
+        }
+        if self.consistency.print_collect | self.consistency.write_collect.is_some() {
+            return Command::Collect(CollectArgs {
+                pgn_file,
+                print_collect: self.consistency.print_collect,
+                write_collect: self.consistency.write_collect.take(),
+            });
+        }
+        if self.export.is_set() {
+            return Command::Export(ExportArgs {
+                pgn_file,
+                consistency: self.check_args(),
+                export_csv: self.export.export_csv.take(),
+                load_csv: self.export.load_csv,
+                rebuild: self.database.rebuild,
+                connection: self.connection(database_url),
+                export_parquet: self.export.export_parquet.take(),
+            });
+        }
+        if self.consistency.check & !self.database.dry_run && database_url.is_none() {
+            return Command::Check(CheckCommandArgs {
+                pgn_file,
+                rejects: self.rejects.take(),
+            });
+        }
+        Command::Ingest(IngestArgs {
+            pgn_file,
+            consistency: self.check_args(),
+            database: self.insert_args(database_url),
+            dry_run: self.database.dry_run,
+            batch_size: self.database.batch_size,
+        })
+    }
+
+    /// Translates the flat arguments into the command they ran, along with the arguments shared by every command, connecting to the database at the url, if any.
+    pub fn into_args(mut self, database_url: Option<String>) -> CLIArgs {
+        CLIArgs {
+            command: self.command(database_url.as_ref()),
+            silent: self.silent,
+            log_file: self.log_file,
+            log_level: None,
+            log_format: LogFormat::default(),
+            log_modules: Vec::new(),
+            log_max_size: None,
+            log_files: 5,
+            error_report: self.error_report,
+            db_envfile: self.database.db_envfile,
+            config_file: None,
+            verbose: self.verbose,
+            config: Config::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+
+    use super::{Command, LegacyArgs};
+
+    /// Tests that the flat flags are translated into the command they ran.
+    #[test]
+    fn command_test() {
+        let translate = |arguments: &[&str]| {
+            LegacyArgs::try_parse_from([&["lichess"], arguments].concat())
+                .unwrap()
+                .into_args(None)
+                .command
+        };
+        assert_eq!(translate(&["--migrate"]).name(), "migrate");
+        assert_eq!(translate(&["--purge-run", "1"]).name(), "purge");
+        assert_eq!(translate(&["-c", "-p", "game.pgn"]).name(), "collect");
+        let Command::Sample(sample) = translate(&["-c", "-f", "-m", "5", "game.pgn"]) else {
+            panic!("The sample size should translate into the sample command.");
+        };
+        assert_eq!(sample.sample, 5);
+        assert_eq!(sample.pgn_file.to_str(), Some("game.pgn"));
+        assert!(sample.consistency.check & sample.consistency.force_insert);
+        let Command::Ingest(ingest) = translate(&["-c", "--dry-run", "-b", "8", "game.pgn"]) else {
+            panic!("A dry run should translate into the ingest command.");
+        };
+        assert!(ingest.dry_run);
+        assert_eq!(ingest.batch_size, 8);
+        let Command::Export(export) = translate(&["-c", "--export-csv", "csv", "game.pgn"]) else {
+            panic!("An export directory should translate into the export command.");
+        };
+        assert_eq!(export.export_csv.unwrap().to_str(), Some("csv"));
+        assert!(export.consistency.check & export.export_parquet.is_none());
+        assert_eq!(
+            translate(&["--export-parquet", "parquet", "game.pgn"]).name(),
+            "export"
+        );
+    }
+}
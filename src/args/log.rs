@@ -1,16 +1,38 @@
 //! Configuration of this scrapper's logging.
 
-use std::{error::Error, fs::File, io::BufWriter};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write, stderr},
+    path::PathBuf,
+    sync::Mutex,
+};
 
-use log::{Level, info, trace};
+use clap::ValueEnum;
+use lichess::report::write_json_str;
+use log::{
+    Level, LevelFilter, Log, Metadata, Record, info,
+    kv::{self, Key, Value, VisitSource},
+    trace,
+};
 use simplelog::{
-    Color, ColorChoice::Auto as AutoColor, CombinedLogger, ConfigBuilder, TermLogger,
+    Color, ColorChoice::Auto as AutoColor, ConfigBuilder, TermLogger,
     TerminalMode::Mixed as MixedTerm, WriteLogger,
 };
-use time::macros::format_description;
+use time::{OffsetDateTime, macros::format_description};
 
 use super::CLIArgs;
 
+/// Format of the log records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines, colored in the terminal.
+    #[default]
+    Text,
+    /// JSON lines with the time, level, component and message of each record, and the game index and error kind of the errors of the games.
+    Json,
+}
+
 impl CLIArgs {
     /// Configures and initializes the loggers for the scrapper.
     ///
@@ -20,7 +42,10 @@ impl CLIArgs {
         if self.silent & self.log_file.is_none() {
             return Ok(());
         }
-        let level_filter = self.verbose.log_level_filter();
+        let level_filter = match self.log_level {
+            Some(level) if !self.verbose.is_present() => level,
+            _ => self.verbose.log_level_filter(),
+        };
 
         let config = ConfigBuilder::new()
             .set_level_color(Level::Trace, Some(Color::Yellow))
@@ -32,26 +57,351 @@ impl CLIArgs {
                 "[[[hour]:[minute]:[second].[subsecond digits:3]]"
             ))
             .build();
-        match (self.silent, &self.log_file) {
-            (false, Some(log_file)) => CombinedLogger::init(vec![
-                TermLogger::new(level_filter, config.clone(), MixedTerm, AutoColor),
-                WriteLogger::new(
-                    level_filter,
-                    config,
-                    BufWriter::new(File::create(log_file)?),
-                ),
-            ])?,
-            (false, None) => TermLogger::init(level_filter, config, MixedTerm, AutoColor)?,
-            (true, Some(log_file)) => WriteLogger::init(
-                level_filter,
-                config,
-                BufWriter::new(File::create(log_file)?),
-            )?,
-            _ => (),
+        let mut loggers = Vec::<Box<dyn Log>>::new();
+        if !self.silent {
+            loggers.push(match self.log_format {
+                LogFormat::Text => {
+                    TermLogger::new(LevelFilter::Trace, config.clone(), MixedTerm, AutoColor)
+                }
+                LogFormat::Json => Box::new(JsonLogger::new(stderr())),
+            });
+        }
+        if let Some(log_file) = &self.log_file {
+            let file = RotatingFile::create(log_file.clone(), self.log_max_size, self.log_files)?;
+            loggers.push(match self.log_format {
+                LogFormat::Text => WriteLogger::new(LevelFilter::Trace, config, file),
+                LogFormat::Json => Box::new(JsonLogger::new(file)),
+            });
         }
+        let logger = ModuleLogger {
+            level: level_filter,
+            modules: self.log_modules.clone(),
+            loggers,
+        };
+        log::set_max_level(logger.max_level());
+        log::set_boxed_logger(Box::new(logger))?;
         trace!("CLIArgs init_loggers function.");
         info!("Logging initialized.");
 
         Ok(())
     }
 }
+
+/// Logger that passes the records on to its loggers if they are at the level of their module or above.
+struct ModuleLogger {
+    /// Level of the modules without one of their own.
+    level: LevelFilter,
+    /// Modules with a level of their own, which are matched by any part of the path of the module a record comes from.
+    modules: Vec<(String, LevelFilter)>,
+    /// Loggers the records are passed on to.
+    loggers: Vec<Box<dyn Log>>,
+}
+
+impl ModuleLogger {
+    /// Gets the level of the module a record comes from, which is the one of the longest module given that is part of its path.
+    fn level(&self, target: &str) -> LevelFilter {
+        let path = target.split("::").collect::<Vec<_>>();
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                let module = module.split("::").collect::<Vec<_>>();
+                path.windows(module.len()).any(|part| part == module)
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.level, |&(_, level)| level)
+    }
+
+    /// Gets the most verbose level of all the modules.
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.level, Ord::max)
+    }
+}
+
+impl Log for ModuleLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            for logger in &self.loggers {
+                logger.log(record);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        for logger in &self.loggers {
+            logger.flush();
+        }
+    }
+}
+
+/// Logger that writes each record as a JSON line.
+struct JsonLogger<W: Write + Send> {
+    /// Writer the lines are written to.
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLogger<W> {
+    /// Creates a logger that writes to the writer.
+    const fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<W: Write + Send> Log for JsonLogger<W> {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let mut line = Vec::new();
+        if write_json_record(&mut line, record).is_ok()
+            && let Ok(mut writer) = self.writer.lock()
+        {
+            let _ = writer.write_all(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// Writes the record as a JSON line, with its time, level, component, fields and message. The component is the path of the module it comes from, without the crate.
+///
+/// # Errors
+/// Will return [`io::Error`] if the writing fails.
+fn write_json_record<W: Write>(writer: &mut W, record: &Record<'_>) -> io::Result<()> {
+    let time = OffsetDateTime::now_utc()
+        .format(format_description!(
+            "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
+        ))
+        .map_err(io::Error::other)?;
+    let target = record.target();
+    let component = target.split_once("::").map_or(target, |(_, module)| module);
+    write!(
+        writer,
+        "{{\"time\":\"{time}\",\"level\":\"{}\",\"component\":",
+        record.level()
+    )?;
+    write_json_str(writer, component)?;
+    let mut fields = JsonFields {
+        writer: &mut *writer,
+        result: Ok(()),
+    };
+    let _ = record.key_values().visit(&mut fields);
+    fields.result?;
+    write!(writer, ",\"message\":")?;
+    write_json_str(writer, &record.args().to_string())?;
+    writeln!(writer, "}}")
+}
+
+/// Visitor of the fields of a record that writes them as members of a JSON object, numbers and booleans as such and the rest as strings.
+struct JsonFields<'a, W: Write> {
+    /// Writer the members are written to.
+    writer: &'a mut W,
+    /// Result of the writing, since the visitor can only return errors of the fields.
+    result: io::Result<()>,
+}
+
+impl<'kvs, W: Write> VisitSource<'kvs> for JsonFields<'_, W> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.result = write!(self.writer, ",")
+            .and_then(|()| write_json_str(self.writer, key.as_str()))
+            .and_then(|()| write!(self.writer, ":"))
+            .and_then(|()| {
+                if let Some(number) = value.to_u64() {
+                    write!(self.writer, "{number}")
+                } else if let Some(number) = value.to_i64() {
+                    write!(self.writer, "{number}")
+                } else if let Some(boolean) = value.to_bool() {
+                    write!(self.writer, "{boolean}")
+                } else {
+                    write_json_str(self.writer, &value.to_string())
+                }
+            });
+        self.result
+            .as_ref()
+            .map_err(|_| kv::Error::msg("the field could not be written"))?;
+        Ok(())
+    }
+}
+
+/// Log file that is flushed at the end of every line and rotated when it grows over a size, keeping the previous ones as the file followed by .1, .2 and so on, the lowest being the newest.
+struct RotatingFile {
+    /// Path to the current file.
+    path: PathBuf,
+    /// Writer of the current file.
+    file: BufWriter<File>,
+    /// Number of bytes written to the current file.
+    size: u64,
+    /// Size over which the file is rotated, if any.
+    max_size: Option<u64>,
+    /// Number of previous files to keep.
+    files: usize,
+}
+
+impl RotatingFile {
+    /// Creates the log file, truncating it if it exists.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the file could not be created.
+    fn create(path: PathBuf, max_size: Option<u64>, files: usize) -> io::Result<Self> {
+        Ok(Self {
+            file: BufWriter::new(File::create(&path)?),
+            path,
+            size: 0,
+            max_size,
+            files,
+        })
+    }
+
+    /// Gets the path to the previous file with the index.
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Moves the current file to the first previous one, shifting the rest and dropping the oldest, and starts a new one.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the renaming or the creation of the files fail.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for index in (1..self.files).rev() {
+            let previous = self.rotated(index);
+            if previous.exists() {
+                fs::rename(previous, self.rotated(index + 1))?;
+            }
+        }
+        if self.files > 0 {
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = BufWriter::new(File::create(&self.path)?);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        if buf[..written].ends_with(b"\n") {
+            if self.max_size.is_some_and(|max_size| self.size >= max_size) {
+                self.rotate()?;
+            } else {
+                self.file.flush()?;
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Parses a module with a level of its own, as MODULE=LEVEL.
+///
+/// # Errors
+/// Will return a [`String`] describing the error if there is no equals sign or the level is not known.
+pub fn module_level(value: &str) -> Result<(String, LevelFilter), String> {
+    let (module, level) = value
+        .split_once('=')
+        .ok_or_else(|| String::from("the module must be given as MODULE=LEVEL"))?;
+    let level = level
+        .parse()
+        .map_err(|_| format!("unknown level {level:?}"))?;
+    Ok((module.to_owned(), level))
+}
+
+/// Parses a size in bytes, optionally followed by K, M or G for kibibytes, mebibytes or gibibytes.
+///
+/// # Errors
+/// Will return a [`String`] describing the error if the size is not a number with one of those suffixes.
+pub fn size(value: &str) -> Result<u64, String> {
+    let (number, multiplier) = match value.as_bytes().last() {
+        Some(b'K' | b'k') => (&value[..value.len() - 1], 1 << 10),
+        Some(b'M' | b'm') => (&value[..value.len() - 1], 1 << 20),
+        Some(b'G' | b'g') => (&value[..value.len() - 1], 1 << 30),
+        _ => (value, 1),
+    };
+    number
+        .parse::<u64>()
+        .map(|number| number * multiplier)
+        .map_err(|_| {
+            String::from("the size must be a number of bytes, optionally followed by K, M or G")
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use log::{LevelFilter, Record};
+    use pretty_assertions::assert_eq;
+
+    use super::{ModuleLogger, size, write_json_record};
+
+    /// Tests that the modules get the level of the longest module given that is part of their path.
+    #[test]
+    fn level_test() {
+        let logger = ModuleLogger {
+            level: LevelFilter::Info,
+            modules: vec![
+                (String::from("checker"), LevelFilter::Warn),
+                (String::from("adapter"), LevelFilter::Debug),
+                (String::from("adapter::mysql"), LevelFilter::Trace),
+            ],
+            loggers: Vec::new(),
+        };
+        assert_eq!(logger.level("lichess::reader"), LevelFilter::Info);
+        assert_eq!(
+            logger.level("lichess::visitors::checkcollect::checker"),
+            LevelFilter::Warn
+        );
+        assert_eq!(logger.level("lichess::adapter::sqlite"), LevelFilter::Debug);
+        assert_eq!(logger.level("lichess::adapter::mysql"), LevelFilter::Trace);
+        assert_eq!(logger.level("lichess::checkers"), LevelFilter::Info);
+        assert_eq!(logger.max_level(), LevelFilter::Trace);
+        assert_eq!(size("512"), Ok(512));
+        assert_eq!(size("100M"), Ok(100 << 20));
+        assert!(size("1T").is_err());
+    }
+
+    /// Tests that the records are written as JSON lines with their fields.
+    #[test]
+    fn json_test() {
+        let game = 12;
+        let fields = [
+            ("game", log::kv::Value::from(game)),
+            ("kind", "invalid_san".into()),
+        ];
+        let mut line = Vec::new();
+        write_json_record(
+            &mut line,
+            &Record::builder()
+                .args(format_args!("12.3 - Invalid SAN move played: \"Ke3\""))
+                .level(log::Level::Error)
+                .target("lichess::visitors::checkcollect::checker")
+                .key_values(&fields)
+                .build(),
+        )
+        .unwrap();
+        let line = String::from_utf8(line).unwrap();
+        let (_, line) = line.split_once("Z\",").unwrap();
+        assert_eq!(
+            line,
+            "\"level\":\"ERROR\",\"component\":\"visitors::checkcollect::checker\",\"game\":12,\"kind\":\"invalid_san\",\"message\":\"12.3 - Invalid SAN move played: \\\"Ke3\\\"\"}\n"
+        );
+    }
+}
@@ -6,6 +6,7 @@ macro_rules! eattribute {
     ($struct:ident, $array:expr) => {
         crate::attribute_fmt!($struct, $array);
         crate::attribute_err!($struct);
+        crate::attribute_serde!($struct);
 
         impl TryFrom<&[u8]> for $struct {
             type Error = crate::attributes::error::ValuedAttributeParsingError;
@@ -123,6 +124,11 @@ macro_rules! sattribute {
 
         #[doc = concat!(stringify!($struct), "of a Lichess game.")]
         #[derive(Debug, Default, Clone)]
+        #[cfg_attr(
+            any(feature = "chrono-serde", feature = "time-serde"),
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         pub struct $struct(pub String);
 
         impl crate::attributes::attribute::StringAttribute for $struct {
@@ -169,6 +175,11 @@ macro_rules! dtattribute {
 
         #[doc = concat!(stringify!($struct), "of a Lichess game.")]
         #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(
+            any(feature = "chrono-serde", feature = "time-serde"),
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         pub struct $struct(pub $kind);
 
         impl crate::attributes::attribute::DatetimeAttribute for $struct {
@@ -259,6 +270,32 @@ macro_rules! attribute_err {
     };
 }
 
+/// Generates the serde implementations for an attribute, which is represented by the same string it has in the PGN file.
+#[macro_export]
+macro_rules! attribute_serde {
+    ($struct:ident) => {
+        #[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+        impl serde::Serialize for $struct {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        #[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+        impl<'de> serde::Deserialize<'de> for $struct {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                <Self as std::str::FromStr>::from_str(&value).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 /// Methods for a stringy struct attribute.
 pub trait StringAttribute {
     /// Truncates the inner [`String`], removing all its contents.
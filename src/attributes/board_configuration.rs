@@ -23,6 +23,8 @@ use super::error::ValuedAttributeParsingError;
 
 #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
 use mysql::{Params, params};
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
 
 /// Data used for each role for codifing the number of pieces left.
 const ROLE_DATA: [(u16, usize, Role); 5] = [
@@ -47,7 +49,7 @@ const ROLE_DATA: [(u16, usize, Role); 5] = [
 /// - `square` ((0..32).step_by(4) bits - 4 total, 8 squares (0..3 being the 1st square, 28..31 being the 8th square)) - Codifies the piece in each square, in the following manner:
 ///   - `color` (3rd bit - 1 total) - 0 for a black piece, 1 for a white piece.
 ///   - `piece` (2nd-0th bits - 3 total) - The role of the piece, following the same numbering as [`shakmaty::Role`], 0 meaning no piece and 7 being invalid.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BoardConfiguration {
     pub black_left: u16,
     pub white_left: u16,
@@ -147,6 +149,35 @@ impl BoardConfiguration {
         }
     }
 
+    /// Generates the hexadecimal representation of this [`BoardConfiguration`], with 4 digits for `black_left`, 4 digits for `white_left` and 8 digits for each of the rows.
+    pub fn to_hex(&self) -> String {
+        let mut hex = format!("{:04X}{:04X}", self.black_left, self.white_left);
+        for row in self.rows {
+            hex.push_str(&format!("{row:08X}"));
+        }
+        hex
+    }
+
+    /// Tries to parse the hexadecimal representation of a [`BoardConfiguration`].
+    ///
+    /// # Errors
+    /// Will return [`ValuedAttributeParsingError`] if the string slice is not 72 hexadecimal digits long.
+    pub fn from_hex(value: &str) -> Result<Self, ValuedAttributeParsingError> {
+        let error = || ValuedAttributeParsingError::from_inner_utf8(ERROR, value.to_owned());
+        if (value.len() != 72) | !value.is_ascii() {
+            return Err(error());
+        }
+        let mut configuration = Self {
+            black_left: u16::from_str_radix(&value[..4], 16).map_err(|_| error())?,
+            white_left: u16::from_str_radix(&value[4..8], 16).map_err(|_| error())?,
+            rows: [0; 8],
+        };
+        for (row, i) in configuration.rows.iter_mut().zip((8..72).step_by(8)) {
+            *row = u32::from_str_radix(&value[i..i + 8], 16).map_err(|_| error())?;
+        }
+        Ok(configuration)
+    }
+
     #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
     /// Prepares the parameters for MySQL insertion and selection of this data.
     pub fn as_params(&self) -> Params {
@@ -186,6 +217,20 @@ impl BitXor for BoardConfiguration {
     }
 }
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl Serialize for BoardConfiguration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl<'de> Deserialize<'de> for BoardConfiguration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_hex(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
 attribute_fmt!(
     BoardConfiguration,
     "left pieces (3 bits for each piece and color, except the pawns, which need 4 bits) rows (8 rows, one of 32 bits, four bits for each square, the first bit being whether it's a white or a black piece and the following three coinciding with the value of shakmaty's Role enum)"
@@ -10,6 +10,9 @@ use std::fmt::Display;
 
 use super::AttributeFormat;
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
 pub mod clk;
 pub mod date;
 pub mod utc_date;
@@ -17,7 +20,10 @@ pub mod utc_time;
 
 #[cfg(feature = "chrono")]
 mod chrono {
-    use chrono::{NaiveDate as CDate, NaiveDateTime as CDateTime, NaiveTime as CTime, ParseError};
+    use chrono::{
+        Datelike, NaiveDate as CDate, NaiveDateTime as CDateTime, NaiveTime as CTime, ParseError,
+        Timelike,
+    };
 
     const CDATE_FORMAT: &str = "%Y.%m.%d";
     const CTIME_FORMAT: &str = "%H:%M:%S";
@@ -33,6 +39,15 @@ mod chrono {
         pub fn parse(input: &str) -> Result<Self, ParseError> {
             CDate::parse_from_str(input, CDATE_FORMAT).map(|date| Self(date))
         }
+
+        pub fn to_pgn(&self) -> String {
+            self.0.format(CDATE_FORMAT).to_string()
+        }
+
+        /// Gets the year and the month of the date.
+        pub fn year_month(&self) -> (i32, u8) {
+            (self.0.year(), self.0.month() as u8)
+        }
     }
 
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -46,6 +61,20 @@ mod chrono {
         pub fn parse(input: &str) -> Result<Self, ParseError> {
             CTime::parse_from_str(input, CTIME_FORMAT).map(|time| Self(time))
         }
+
+        pub fn to_pgn(&self) -> String {
+            self.0.format(CTIME_FORMAT).to_string()
+        }
+
+        /// Gets the number of seconds since midnight.
+        pub fn seconds(&self) -> u32 {
+            self.0.num_seconds_from_midnight()
+        }
+
+        /// Creates a time from the number of seconds since midnight, if it is less than a day.
+        pub fn from_seconds(seconds: u32) -> Option<Self> {
+            CTime::from_num_seconds_from_midnight_opt(seconds, 0).map(Self)
+        }
     }
 
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +85,11 @@ mod chrono {
         pub fn new(date: Date, time: Time) -> Self {
             Self(CDateTime::new(date.0, time.0))
         }
+
+        /// Gets the number of seconds since the Unix epoch, taking the value as UTC.
+        pub fn timestamp(&self) -> i64 {
+            self.0.and_utc().timestamp()
+        }
     }
 }
 
@@ -88,6 +122,17 @@ mod time {
         pub fn parse(input: &str) -> Result<Self, Parse> {
             TDate::parse(input, TDATE_FORMAT).map(|date| Self(date))
         }
+
+        pub fn to_pgn(&self) -> String {
+            self.0
+                .format(TDATE_FORMAT)
+                .expect("A date must always be formattable.")
+        }
+
+        /// Gets the year and the month of the date.
+        pub fn year_month(&self) -> (i32, u8) {
+            (self.0.year(), self.0.month().into())
+        }
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -107,6 +152,26 @@ mod time {
         pub fn parse(input: &str) -> Result<Self, Parse> {
             TTime::parse(input, TTIME_FORMAT).map(|time| Self(time))
         }
+
+        pub fn to_pgn(&self) -> String {
+            self.0
+                .format(TTIME_FORMAT)
+                .expect("A time must always be formattable.")
+        }
+
+        /// Gets the number of seconds since midnight.
+        pub fn seconds(&self) -> u32 {
+            let (hour, minute, second) = self.0.as_hms();
+            u32::from(hour) * 3600 + u32::from(minute) * 60 + u32::from(second)
+        }
+
+        /// Creates a time from the number of seconds since midnight, if it is less than a day.
+        pub fn from_seconds(seconds: u32) -> Option<Self> {
+            let hour = u8::try_from(seconds / 3600).ok()?;
+            TTime::from_hms(hour, (seconds / 60 % 60) as u8, (seconds % 60) as u8)
+                .ok()
+                .map(Self)
+        }
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -123,6 +188,11 @@ mod time {
         pub fn new(date: Date, time: Time) -> Self {
             Self(TDateTime::new(date.0, time.0))
         }
+
+        /// Gets the number of seconds since the Unix epoch, taking the value as UTC.
+        pub fn timestamp(&self) -> i64 {
+            self.0.assume_utc().unix_timestamp()
+        }
     }
 }
 
@@ -150,5 +220,33 @@ impl Display for Datetime {
     }
 }
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_pgn())
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_pgn())
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
 pub const DATE_FORMAT: AttributeFormat = AttributeFormat::Str("{:YYYY}.{:MM}.{:DD}");
 pub const TIME_FORMAT: AttributeFormat = AttributeFormat::Str("{:hh}:{:mm}:{:ss}");
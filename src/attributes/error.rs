@@ -13,6 +13,11 @@ impl AttributeParsingError {
     pub const fn new(parsed_kind: AttributeKind) -> Self {
         Self(parsed_kind)
     }
+
+    /// Getter of the [`AttributeKind`] that was tried to parse.
+    pub const fn kind(&self) -> AttributeKind {
+        self.0
+    }
 }
 
 impl Display for AttributeParsingError {
@@ -66,6 +71,16 @@ impl ValuedAttributeParsingError {
             value: value.into_bytes(),
         }
     }
+
+    /// Getter of the [`AttributeKind`] that was tried to parse.
+    pub const fn kind(&self) -> AttributeKind {
+        self.inner.kind()
+    }
+
+    /// Getter of the bytes value the error has occurred with.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
 }
 
 impl Display for ValuedAttributeParsingError {
@@ -86,7 +101,12 @@ impl Error for ValuedAttributeParsingError {}
 #[macro_export]
 macro_rules! loneerror {
     ($str:literal, $self:ident) => {
-        log::error!(concat!("{} - ", $str), $self.games);
+        log::error!(game = $self.games, kind = $crate::report::ErrorCategory::Inconsistency.as_str(); concat!("{} - ", $str), $self.games);
+        $self.report.push($crate::report::ErrorRecord::new(
+            $self.games,
+            $crate::report::ErrorCategory::Inconsistency,
+            $str,
+        ));
         $self.has_errors = true;
     };
 }
@@ -95,16 +115,57 @@ macro_rules! loneerror {
 #[macro_export]
 macro_rules! nullerror {
     ($str:literal, $self:ident) => {
-        log::error!(concat!("{} - ", $str, " is null"), $self.games);
+        log::error!(game = $self.games, kind = $crate::report::ErrorCategory::NullHeader.as_str(); concat!("{} - ", $str, " is null"), $self.games);
+        $self.report.push(
+            $crate::report::ErrorRecord::new(
+                $self.games,
+                $crate::report::ErrorCategory::NullHeader,
+                concat!($str, " is null"),
+            )
+            .with_key($str.as_bytes()),
+        );
         $self.has_errors = true;
     };
 }
 
-/// Logs an error with context and the game it happened at.
+/// Logs an error with context and the game it happened at, optionally with the header or comment key and raw value it happened at.
 #[macro_export]
 macro_rules! valuederror {
     ($self:ident, $error:ident) => {
-        log::error!("{} - {}", $self.games, $error);
+        let record = $crate::report::ToErrorRecord::to_record(&$error, $self.games);
+        log::error!(game = $self.games, kind = record.category.as_str(); "{} - {}", $self.games, $error);
+        $self.report.push(record);
+        $self.has_errors = true;
+    };
+    ($self:ident, $error:ident, $key:expr, $value:expr) => {
+        let record = $crate::report::ToErrorRecord::to_record(&$error, $self.games);
+        log::error!(game = $self.games, kind = record.category.as_str(); "{} - {}", $self.games, $error);
+        $self.report.push(record.with_key($key).with_value($value));
+        $self.has_errors = true;
+    };
+}
+
+/// Logs an error of a category with the header or comment key and raw value it happened at, optionally showing some bytes after the message in the log.
+#[macro_export]
+macro_rules! keyederror {
+    ($self:ident, $category:ident, $message:expr, $key:expr, $value:expr) => {
+        let message = $message;
+        log::error!(game = $self.games, kind = $crate::report::ErrorCategory::$category.as_str(); "{} - {}", $self.games, message);
+        $self.report.push(
+            $crate::report::ErrorRecord::new($self.games, $crate::report::ErrorCategory::$category, message)
+                .with_key($key)
+                .with_value($value),
+        );
+        $self.has_errors = true;
+    };
+    ($self:ident, $category:ident, $message:expr, $key:expr, $value:expr, $shown:expr) => {
+        let message = $message;
+        log::error!(game = $self.games, kind = $crate::report::ErrorCategory::$category.as_str(); "{} - {}: {} <- {:?}", $self.games, message, String::from_utf8_lossy($shown), $shown);
+        $self.report.push(
+            $crate::report::ErrorRecord::new($self.games, $crate::report::ErrorCategory::$category, message)
+                .with_key($key)
+                .with_value($value),
+        );
         $self.has_errors = true;
     };
 }
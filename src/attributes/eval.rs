@@ -72,3 +72,4 @@ impl Display for Eval {
 }
 
 crate::tattribute!(Eval, "<{f32}|#{i8}>");
+crate::attribute_serde!(Eval);
@@ -51,6 +51,28 @@ pub enum AttributeKind {
 }
 
 impl AttributeKind {
+    /// Getter of the name of the kind of attribute, as its identifier.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Result => "Result",
+            Self::Termination => "Termination",
+            Self::TimeControl => "TimeControl",
+            Self::Date => "Date",
+            Self::UTCDate => "UTCDate",
+            Self::UTCTime => "UTCTime",
+            Self::RuleSet => "RuleSet",
+            Self::Opening => "Opening",
+            Self::Eco => "Eco",
+            Self::Player => "Player",
+            Self::Elo => "Elo",
+            Self::Title => "Title",
+            Self::Eval => "Eval",
+            Self::Clk => "Clk",
+            Self::MoveDescriptor => "MoveDescriptor",
+            Self::BoardConfiguration => "BoardConfiguration",
+        }
+    }
+
     /// Getter of the format of the kind of attribute.
     const fn format(&self) -> AttributeFormat {
         match self {
@@ -15,7 +15,7 @@ use shakmaty::{
     Color,
     Move::{self, Castle, EnPassant, Normal, Put},
     Position,
-    Role::{King, Pawn},
+    Role::{self, King, Pawn},
     Square,
     san::{
         SanPlus,
@@ -27,6 +27,9 @@ use crate::{attribute_err, attribute_fmt};
 
 use super::error::{AttributeParsingError, ValuedAttributeParsingError};
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
 /// Mask that indicates there has been a check.
 const CHECK: u32 = 0b01 << 8;
 /// Mask that indicates there has been a checkmate (check, and checkmate).
@@ -181,6 +184,62 @@ impl MoveDescriptor {
         };
         ((self.color as u32) << 31) | r#move | suffix | (self.nag.0 as u32)
     }
+
+    /// Reconstructs a move from its u32 representation, as generated by [`MoveDescriptor::to_u32`].
+    ///
+    /// # Errors
+    /// Will return [`AttributeParsingError`] if the given [`u32`] does not represent a valid move.
+    pub fn from_u32(value: u32) -> Result<Self, AttributeParsingError> {
+        let role = |bits: u32| Role::try_from(bits & 0b111).map_err(|_| ERROR);
+        let (from, captured, to, promoted) = (
+            Square::new((value >> 22) & 0b111111),
+            (value >> 19) & 0b111,
+            Square::new((value >> 13) & 0b111111),
+            (value >> 10) & 0b111,
+        );
+        let r#move = if promoted >= King as u32 {
+            Castle {
+                king: from,
+                rook: to,
+            }
+        } else if captured == 7 {
+            EnPassant { from, to }
+        } else {
+            Normal {
+                role: role(value >> 28)?,
+                from,
+                capture: (captured != 0).then(|| role(captured)).transpose()?,
+                to,
+                promotion: (promoted != 0).then(|| role(promoted)).transpose()?,
+            }
+        };
+        let suffix = match value & CHECKMATE {
+            0 => None,
+            CHECK => Some(Check),
+            CHECKMATE => Some(Checkmate),
+            _ => return Err(ERROR),
+        };
+        Ok(Self {
+            r#move,
+            suffix,
+            nag: Nag(value as u8),
+            color: Color::from_white((value >> 31) == 1),
+        })
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl Serialize for MoveDescriptor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.to_u32())
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl<'de> Deserialize<'de> for MoveDescriptor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_u32(u32::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
 }
 
 attribute_fmt!(MoveDescriptor, "SAN notation");
@@ -280,4 +339,21 @@ mod test {
             assert_eq!(value ^ truth, 0);
         }
     }
+
+    /// Tests whether the MoveDescriptor::from_u32 actually reverses MoveDescriptor::to_u32 or not.
+    #[test]
+    pub fn move_from_u32_test() {
+        const TEST_CASES: [u32; 4] = [
+            0b01000010010101001000000100000000,
+            0b10010100101110110110001100000000,
+            0b01100001000000000001100000010111,
+            0b11100001000000001111110000010111,
+        ];
+        for (value, i) in TEST_CASES.into_iter().zip(1..) {
+            let r#move = MoveDescriptor::from_u32(value).map(|r#move| r#move.to_u32());
+            println!("{i:02}: {value:032b}\n    {:032b}", r#move.unwrap_or(0));
+            assert_eq!(r#move.ok(), Some(value));
+        }
+        assert!(MoveDescriptor::from_u32(0b00000000000000000000001000000000).is_err());
+    }
 }
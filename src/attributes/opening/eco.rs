@@ -15,6 +15,10 @@ use mysql::prelude::FromValue;
     any(feature = "time-mysql", feature = "chrono-mysql"),
     derive(FromValue)
 )]
+#[cfg_attr(
+    any(feature = "chrono-serde", feature = "time-serde"),
+    derive(serde::Serialize, serde::Deserialize)
+)]
 #[repr(u8)]
 pub enum EcoChar {
     #[default]
@@ -94,7 +98,7 @@ impl EcoChar {
     }
 }
 
-/// ECO code of an opening.
+/// ECO code of an opening, written and parsed as in the PGN headers: `?` for an unknown one, and its letter followed by its two digits otherwise.
 #[derive(Debug, Clone, Copy)]
 pub struct Eco(pub EcoChar, pub RangedU8<0, 99>);
 
@@ -117,7 +121,7 @@ impl Eco {
             let ch = EcoChar::from_char(char)?;
             Ok(Self(
                 ch,
-                if ch == EcoChar::Q {
+                if ch != EcoChar::Q {
                     Self::parse_eco_num(chars.as_str())?
                 } else {
                     RangedU8::new_static::<0>()
@@ -158,8 +162,47 @@ impl Default for Eco {
 
 impl Display for Eco {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{:02}", self.0.as_char(), self)
+        if self.0 == EcoChar::Q {
+            write!(f, "{}", self.0.as_char())
+        } else {
+            write!(f, "{}{:02}", self.0.as_char(), self.1.get())
+        }
     }
 }
 
 crate::tattribute!(Eco, "<?|[A-E][00-99]>");
+crate::attribute_serde!(Eco);
+
+#[cfg(all(test, any(feature = "chrono-serde", feature = "time-serde")))]
+mod test {
+    use csv::{ReaderBuilder, Writer};
+    use pretty_assertions::assert_eq;
+
+    use super::{Eco, EcoChar};
+
+    /// Tests that the ECO codes, known or not, keep their letter and number through serialization and deserialization.
+    #[test]
+    fn eco_serde_test() {
+        for code in ["B07", "A00", "E99", "?"] {
+            let eco = Eco::from_str(code).unwrap();
+            let mut writer = Writer::from_writer(Vec::new());
+            writer.serialize(eco).unwrap();
+            let serialized = writer.into_inner().unwrap();
+            assert_eq!(
+                String::from_utf8(serialized.clone()).unwrap(),
+                format!("{code}\n")
+            );
+            let deserialized: Eco = ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(serialized.as_slice())
+                .deserialize()
+                .next()
+                .unwrap()
+                .unwrap();
+            assert_eq!(deserialized.0, eco.0);
+            assert_eq!(deserialized.1.get(), eco.1.get());
+        }
+        assert_eq!(Eco::from_str("B07").unwrap().0, EcoChar::B);
+        assert_eq!(Eco::from_str("B07").unwrap().1.get(), 7);
+    }
+}
@@ -4,8 +4,16 @@ use std::{fmt::Display, str::from_utf8};
 
 use super::super::error::AttributeParsingError;
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Serialize};
+
 /// Elo rating from a Lichess player.
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(
+    any(feature = "chrono-serde", feature = "time-serde"),
+    derive(Serialize, Deserialize),
+    serde(transparent)
+)]
 pub struct Elo(pub Option<u16>);
 
 impl Elo {
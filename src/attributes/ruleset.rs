@@ -8,6 +8,8 @@ use super::error::AttributeParsingError;
 
 #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
 use mysql::{Params, params, prelude::FromValue};
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
 
 /// A Lichess game's ruleset.
 #[derive(Debug, Default, Clone)]
@@ -78,6 +80,20 @@ impl RuleSet {
         self.fill_str(from_utf8(value).map_err(|_| ERROR)?)
     }
 
+    /// Generates the event header this [`RuleSet`] was parsed from.
+    pub fn to_event(&self) -> String {
+        match self.kind {
+            RuleSetKind::Game => format!("{} game", self.name),
+            RuleSetKind::Arena => format!(
+                "{} tournament https://lichess.org/tournament/{}",
+                self.name, self.url
+            ),
+            RuleSetKind::Swiss => {
+                format!("{} swiss https://lichess.org/swiss/{}", self.name, self.url)
+            }
+        }
+    }
+
     #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
     /// Prepares the parameters for MySQL insertion of this data.
     pub fn as_insert_params(&self) -> Params {
@@ -104,6 +120,10 @@ impl RuleSet {
     any(feature = "time-mysql", feature = "chrono-mysql"),
     derive(FromValue)
 )]
+#[cfg_attr(
+    any(feature = "chrono-serde", feature = "time-serde"),
+    derive(Serialize, Deserialize)
+)]
 #[repr(u8)]
 pub enum RuleSetKind {
     /// A normal game.
@@ -137,5 +157,23 @@ impl Display for RuleSet {
     }
 }
 
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl Serialize for RuleSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_event())
+    }
+}
+
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+impl<'de> Deserialize<'de> for RuleSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut ruleset = Self::default();
+        ruleset
+            .fill_str(&String::deserialize(deserializer)?)
+            .map_err(D::Error::custom)?;
+        Ok(ruleset)
+    }
+}
+
 attribute_fmt!(RuleSet, "{str} <game|tournament|swiss>[ {url}]");
 attribute_err!(RuleSet);
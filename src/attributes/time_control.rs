@@ -6,7 +6,7 @@ use memchr::memchr;
 
 use super::error::AttributeParsingError;
 
-/// The time control used in a Lichess game.
+/// The time control used in a Lichess game, written and parsed as in the PGN headers: `-` for a game without one, and its total and increment separated by a + sign otherwise.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct TimeControl(pub Option<(u16, u8)>);
 
@@ -60,9 +60,41 @@ impl Display for TimeControl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0 {
             Some((total, increment)) => write!(f, "{total}+{increment}"),
-            None => Ok(()),
+            None => "-".fmt(f),
         }
     }
 }
 
 crate::tattribute!(TimeControl, "<-|{u16}+{u8}>");
+crate::attribute_serde!(TimeControl);
+
+#[cfg(all(test, any(feature = "chrono-serde", feature = "time-serde")))]
+mod test {
+    use csv::{ReaderBuilder, Writer};
+    use pretty_assertions::assert_eq;
+
+    use super::TimeControl;
+
+    /// Tests that the time controls, with or without one, keep their value through serialization and deserialization.
+    #[test]
+    fn time_control_serde_test() {
+        for value in ["300+0", "60+1", "-"] {
+            let time_control = TimeControl::from_str(value).unwrap();
+            let mut writer = Writer::from_writer(Vec::new());
+            writer.serialize(time_control).unwrap();
+            let serialized = writer.into_inner().unwrap();
+            assert_eq!(
+                String::from_utf8(serialized.clone()).unwrap(),
+                format!("{value}\n")
+            );
+            let deserialized: TimeControl = ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(serialized.as_slice())
+                .deserialize()
+                .next()
+                .unwrap()
+                .unwrap();
+            assert_eq!(deserialized.0, time_control.0);
+        }
+    }
+}
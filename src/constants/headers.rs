@@ -47,3 +47,6 @@ pub const WHITE_ELO: &[u8] = b"WhiteElo";
 pub const WHITE_RATING_DIFF: &[u8] = b"WhiteRatingDiff";
 /// White player's title header of a game.
 pub const WHITE_TITLE: &[u8] = b"WhiteTitle";
+
+/// Reason a game was rejected for, added to the games written to a rejects file.
+pub const REJECT_REASON: &[u8] = b"RejectReason";
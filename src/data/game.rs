@@ -23,9 +23,12 @@ use crate::attributes::{
 use mysql::{Params, params};
 #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
 use crate::attributes::datetime::Datetime;
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Serialize};
 
 /// Struct containing all the information of a Lichess game.
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(any(feature = "chrono-serde", feature = "time-serde"), derive(Serialize, Deserialize))]
 pub struct Game {
     /*
     /// The website this game was played at.
@@ -86,6 +89,7 @@ pub struct Game {
     pub has_evaluations: bool,
 
     /// The chess position of this game.
+    #[cfg_attr(any(feature = "chrono-serde", feature = "time-serde"), serde(skip))]
     pub chess: Chess,
 
     /// The board configuration at the end of the game.
@@ -137,6 +141,7 @@ impl Game {
         fc_id: u64,
         white: Option<u64>,
         black: Option<u64>,
+        run_id: Option<u64>,
     ) -> Params {
         let (start_time, increment) = self.time_control.0.unzip();
         params! {
@@ -155,6 +160,54 @@ impl Game {
             "termination" => self.termination,
             "datetime" => Datetime::new(self.utc_date.0, self.utc_time.0).0, "has_clock" => self.has_clock,
             "has_evaluations" => self.has_evaluations,
+            run_id,
         }
     }
 }
+
+#[cfg(all(test, any(feature = "chrono-serde", feature = "time-serde")))]
+mod test {
+    use csv::{Reader, Writer};
+    use pretty_assertions::assert_eq;
+    use shakmaty::{Chess, Position};
+
+    use super::Game;
+    use crate::attributes::{BoardConfiguration, attribute::StringAttribute};
+
+    /// Tests that the games keep every field but their position through serialization and deserialization.
+    #[test]
+    fn game_serde_test() {
+        let mut game = Game {
+            time_control: "300+0".parse().unwrap(),
+            result: "0-1".parse().unwrap(),
+            termination: "Time forfeit".parse().unwrap(),
+            utc_date: "2017.04.01".parse().unwrap(),
+            utc_time: "11:32:01".parse().unwrap(),
+            eco: "B30".parse().unwrap(),
+            black_elo: "2000".parse().unwrap(),
+            white_elo: "2100".parse().unwrap(),
+            white_title: Some("FM".parse().unwrap()),
+            has_clock: true,
+            final_conf: BoardConfiguration::from_board(Chess::default().board()).unwrap(),
+            ..Default::default()
+        };
+        game.opening.fill("Sicilian Defense: Old Sicilian");
+        game.ruleset
+            .fill_str("Rated Bullet tournament https://lichess.org/tournament/yc1WW2Ox")
+            .unwrap();
+        game.black.fill("Costello");
+        game.white.fill("Abbot");
+        let games = [game, Game::default()];
+
+        let mut writer = Writer::from_writer(Vec::new());
+        for game in &games {
+            writer.serialize(game).unwrap();
+        }
+        let serialized = writer.into_inner().unwrap();
+        let deserialized = Reader::from_reader(serialized.as_slice())
+            .deserialize()
+            .collect::<std::result::Result<Vec<Game>, _>>()
+            .unwrap();
+        assert_eq!(format!("{deserialized:?}"), format!("{games:?}"));
+    }
+}
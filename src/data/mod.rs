@@ -5,6 +5,8 @@ pub use r#move::Move;
 
 use log::{error, info, warn};
 use pgn_reader::{Nag, SanPlus};
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Serialize};
 use shakmaty::{Outcome, Position};
 
 use super::{
@@ -16,29 +18,42 @@ use super::{
         comments::{CLK, EVAL},
         headers::{
             BLACK, BLACK_ELO, BLACK_RATING_DIFF, BLACK_TITLE, DATE, ECO, EVENT, LICHESS_ID,
-            OPENING, RESULT, ROUND, SITE, TERMINATION, TIME_CONTROL, UTC_DATE, UTC_TIME, WHITE,
-            WHITE_ELO, WHITE_RATING_DIFF, WHITE_TITLE,
+            OPENING, REJECT_REASON, RESULT, ROUND, SITE, TERMINATION, TIME_CONTROL, UTC_DATE,
+            UTC_TIME, WHITE, WHITE_ELO, WHITE_RATING_DIFF, WHITE_TITLE,
         },
     },
 };
-use crate::{loneerror, valuederror};
+use crate::{
+    keyederror, loneerror,
+    report::{ErrorCategory, ErrorReport, ToErrorRecord},
+    valuederror,
+};
 
 pub mod game;
 pub mod r#move;
+pub mod packed;
 
 /// All the data in a PGN file as it is being read.
 #[derive(Debug)]
+#[cfg_attr(
+    any(feature = "chrono-serde", feature = "time-serde"),
+    derive(Serialize, Deserialize)
+)]
 pub struct Data {
     /// The number of games read.
     pub games: usize,
     /// The current game being analyzed.
     pub game: Game,
     /// The current move being analyzed.
+    #[cfg_attr(any(feature = "chrono-serde", feature = "time-serde"), serde(skip))]
     pub r#move: Move,
     /// The total moves of the current game being analyzed.
     pub moves: Vec<Move>,
     /// Whether processing this data led to any errors.
     pub has_errors: bool,
+    /// Report of the errors found while processing this data.
+    #[cfg_attr(any(feature = "chrono-serde", feature = "time-serde"), serde(skip))]
+    pub report: ErrorReport,
 }
 
 impl Default for Data {
@@ -50,6 +65,7 @@ impl Default for Data {
             r#move: Move::default(),
             moves: Vec::default(),
             has_errors: false,
+            report: ErrorReport::default(),
         }
     }
 }
@@ -72,11 +88,16 @@ impl Data {
 
         match MoveDescriptor::from_and_play_san(&san, &mut self.game.chess) {
             Ok(value) => self.r#move.descriptor = value,
-            Err(_) => {
+            Err(e) => {
                 error!(
+                    game = self.games,
+                    kind = ErrorCategory::InvalidSan.as_str();
                     "{}.{} - Invalid SAN move played: {san}",
                     self.games, self.r#move.num
                 );
+                let mut record = e.to_record(self.games);
+                record.category = ErrorCategory::InvalidSan;
+                self.report.push(record);
                 self.has_errors = true;
             }
         }
@@ -88,23 +109,17 @@ impl Data {
             CLK => match Clk::try_from(value) {
                 Ok(value) => self.r#move.clk = Some(value),
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             EVAL => match Eval::try_from(value) {
                 Ok(value) => self.r#move.eval = Some(value),
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             key => {
-                error!(
-                    "{} - New comment found: {} <- {:?}",
-                    self.games,
-                    String::from_utf8_lossy(key),
-                    key
-                );
-                self.has_errors = true;
+                keyederror!(self, UnknownComment, "New comment found", key, value, key);
             }
         }
     }
@@ -112,100 +127,94 @@ impl Data {
     /// Processes the header of a game, parsing the values of its fields.
     pub fn process_header(&mut self, key: &[u8], value: &[u8]) {
         match key {
-            SITE => (),
-            LICHESS_ID => (),
+            SITE | LICHESS_ID => self.report.set_lichess_id(value),
             TIME_CONTROL => match TimeControl::try_from(value) {
                 Ok(value) => self.game.time_control = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             RESULT => match ResultAttr::try_from(value) {
                 Ok(value) => self.game.result = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             TERMINATION => match Termination::try_from(value) {
                 Ok(value) => self.game.termination = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             DATE => (),
             UTC_DATE => match UTCDate::try_from(value) {
                 Ok(value) => self.game.utc_date = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             UTC_TIME => match UTCTime::try_from(value) {
                 Ok(value) => self.game.utc_time = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             OPENING => {
                 if let Err(e) = self.game.opening.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             ECO => match Eco::try_from(value) {
                 Ok(value) => self.game.eco = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             EVENT => {
                 if let Err(e) = self.game.ruleset.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             ROUND => (),
+            REJECT_REASON => (),
             BLACK => {
                 if let Err(e) = self.game.black.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             BLACK_ELO => match Elo::try_from(value) {
                 Ok(value) => self.game.black_elo = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             BLACK_RATING_DIFF => (),
             BLACK_TITLE => match Title::try_from(value) {
                 Ok(value) => self.game.black_title = Some(value),
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             WHITE => {
                 if let Err(e) = self.game.white.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             WHITE_ELO => match Elo::try_from(value) {
                 Ok(value) => self.game.white_elo = value,
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             WHITE_RATING_DIFF => (),
             WHITE_TITLE => match Title::try_from(value) {
                 Ok(value) => self.game.white_title = Some(value),
                 Err(e) => {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             },
             key => {
-                error!(
-                    "{} - New header found: {} <- {:?}",
-                    self.games,
-                    String::from_utf8_lossy(key),
-                    key
-                );
-                self.has_errors = true;
+                keyederror!(self, UnknownHeader, "New header found", key, value, key);
             }
         }
     }
@@ -4,9 +4,15 @@ use crate::attributes::{Clk, Eval, MoveDescriptor};
 
 #[cfg(any(feature = "time-mysql", feature = "chrono-mysql"))]
 use mysql::{Params, params};
+#[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
+use serde::{Deserialize, Serialize};
 
 /// Struct containing all the information of a Lichess game's move.
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    any(feature = "chrono-serde", feature = "time-serde"),
+    derive(Serialize, Deserialize)
+)]
 pub struct Move {
     /// Number of the move, it is only valid if nonzero.
     pub num: usize,
@@ -50,3 +56,35 @@ impl Move {
         }
     }
 }
+
+#[cfg(all(test, any(feature = "chrono-serde", feature = "time-serde")))]
+mod test {
+    use csv::{Reader, Writer};
+    use pretty_assertions::assert_eq;
+
+    use super::Move;
+
+    /// Tests that the moves keep every field through serialization and deserialization, with and without evaluation and clock.
+    #[test]
+    fn move_serde_test() {
+        let mut moves = vec![Move::default(); 3];
+        moves[1].num = 12;
+        moves[1].descriptor =
+            crate::attributes::MoveDescriptor::from_u32(0b01000010010101001000000100000000)
+                .unwrap();
+        moves[1].eval = Some("-1.25".parse().unwrap());
+        moves[1].clk = Some("0:03:00".parse().unwrap());
+        moves[2].num = 40;
+        moves[2].eval = Some("#-3".parse().unwrap());
+        let mut writer = Writer::from_writer(Vec::new());
+        for r#move in &moves {
+            writer.serialize(r#move).unwrap();
+        }
+        let serialized = writer.into_inner().unwrap();
+        let deserialized = Reader::from_reader(serialized.as_slice())
+            .deserialize()
+            .collect::<Result<Vec<Move>, _>>()
+            .unwrap();
+        assert_eq!(format!("{deserialized:?}"), format!("{moves:?}"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Compact storage of the moves of a game as a single blob, which is kept in the Moves column of the Game table instead of one row of the Move table per ply.
+//!
+//! The blob starts with a header of two bytes, followed by its payload:
+//! - `version` (1 byte) - Version of the layout, which is [`VERSION`].
+//! - `codec` (1 byte) - Compression of the payload: [`RAW`] if it is stored as is, [`ZSTD`] if it is a zstd frame.
+//!
+//! Once decompressed, the payload is the number of moves followed by every move in order, where the number of each move is its position plus one:
+//! - `count` (varint) - Number of moves of the game.
+//! - `descriptor` (varint) - The move as given by [`MoveDescriptor::to_u32`].
+//! - `flags` (1 byte) - Which fields follow: [`EVAL_NUMERIC`], [`EVAL_CHECKMATE`] and [`CLOCK`].
+//! - `eval` (4 bytes or 1 byte, if flagged) - The numeric evaluation as a little endian [`f32`], or the moves until checkmate as an [`i8`].
+//! - `clock` (varint, if flagged) - The clock time as seconds.
+//!
+//! Varints are unsigned LEB128: 7 bits per byte, least significant first, with the highest bit set on every byte but the last.
+
+use std::{error::Error, fmt::Display};
+
+use super::Move;
+use crate::attributes::{Clk, Eval, MoveDescriptor, datetime::Time};
+
+/// Version of the layout of the blobs written by [`pack`].
+pub const VERSION: u8 = 1;
+/// Codec of a payload stored without compression.
+pub const RAW: u8 = 0;
+/// Codec of a payload compressed as a zstd frame.
+pub const ZSTD: u8 = 1;
+/// Flag of a move with a numeric evaluation.
+pub const EVAL_NUMERIC: u8 = 0b001;
+/// Flag of a move with an evaluation of moves until checkmate.
+pub const EVAL_CHECKMATE: u8 = 0b010;
+/// Flag of a move with a clock time.
+pub const CLOCK: u8 = 0b100;
+/// Level of the zstd compression of the payloads.
+#[cfg(feature = "zstd")]
+const ZSTD_LEVEL: i32 = 3;
+
+/// Error of the unpacking of a blob of moves.
+#[derive(Debug)]
+pub enum PackingError {
+    /// The blob ended before the value at the given position of the payload.
+    Truncated(usize),
+    /// The blob has a version of the layout this program doesn't know.
+    Version(u8),
+    /// The payload has a codec this program doesn't know, or whose feature isn't enabled.
+    Codec(u8),
+    /// A move has a descriptor that doesn't represent a valid move.
+    Descriptor(u32),
+    /// A move has a clock time of a day or longer.
+    Clock(u64),
+    /// The payload couldn't be decompressed.
+    Decompression(std::io::Error),
+}
+
+impl Display for PackingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated(position) => {
+                write!(f, "the packed moves end abruptly at byte {position}")
+            }
+            Self::Version(version) => write!(f, "unknown version {version} of the packed moves"),
+            Self::Codec(codec) => write!(f, "unsupported codec {codec} of the packed moves"),
+            Self::Descriptor(descriptor) => write!(f, "invalid move descriptor {descriptor}"),
+            Self::Clock(seconds) => write!(f, "invalid clock of {seconds} seconds"),
+            Self::Decompression(e) => write!(f, "unable to decompress the packed moves: {e}"),
+        }
+    }
+}
+
+impl Error for PackingError {}
+
+/// Appends an unsigned integer to a buffer as a varint.
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Reader of the values of a payload, which keeps its position.
+struct Cursor<'a> {
+    /// The payload being read.
+    bytes: &'a [u8],
+    /// Position of the next byte to read.
+    position: usize,
+}
+
+impl Cursor<'_> {
+    /// Reads the next bytes of the payload.
+    ///
+    /// # Errors
+    /// Will return [`PackingError::Truncated`] if there are fewer bytes left.
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], PackingError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + N)
+            .ok_or(PackingError::Truncated(self.position))?;
+        self.position += N;
+        Ok(bytes.try_into().expect("The slice must have N bytes."))
+    }
+
+    /// Reads the next varint of the payload.
+    ///
+    /// # Errors
+    /// Will return [`PackingError::Truncated`] if the payload ends before the varint does.
+    fn varint(&mut self) -> Result<u64, PackingError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let [byte] = self.take()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(PackingError::Truncated(self.position))
+    }
+}
+
+/// Packs the moves of a game into a blob, compressing its payload if the feature zstd is enabled.
+pub fn pack(moves: &[Move]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + moves.len() * 8);
+    write_varint(&mut payload, moves.len() as u64);
+    for r#move in moves {
+        write_varint(&mut payload, u64::from(r#move.descriptor.to_u32()));
+        let mut flags = 0;
+        match r#move.eval {
+            Some(Eval::Numeric(_)) => flags |= EVAL_NUMERIC,
+            Some(Eval::Checkmate(_)) => flags |= EVAL_CHECKMATE,
+            None => {}
+        }
+        if r#move.clk.is_some() {
+            flags |= CLOCK;
+        }
+        payload.push(flags);
+        match r#move.eval {
+            Some(Eval::Numeric(num)) => payload.extend(num.to_le_bytes()),
+            Some(Eval::Checkmate(checkmate)) => payload.extend(checkmate.to_le_bytes()),
+            None => {}
+        }
+        if let Some(clk) = r#move.clk {
+            write_varint(&mut payload, u64::from(clk.0.seconds()));
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    if let Ok(compressed) = zstd::bulk::compress(&payload, ZSTD_LEVEL)
+        && compressed.len() < payload.len()
+    {
+        return [VERSION, ZSTD].into_iter().chain(compressed).collect();
+    }
+    [VERSION, RAW].into_iter().chain(payload).collect()
+}
+
+/// Unpacks the moves of a game from a blob written by [`pack`].
+///
+/// # Errors
+/// Will return [`PackingError`] if the blob doesn't follow the layout, has an unknown version or codec, or can't be decompressed.
+pub fn unpack(blob: &[u8]) -> Result<Vec<Move>, PackingError> {
+    let [version, codec, ..] = *blob else {
+        return Err(PackingError::Truncated(blob.len()));
+    };
+    if version != VERSION {
+        return Err(PackingError::Version(version));
+    }
+    #[cfg(feature = "zstd")]
+    let decompressed;
+    let bytes = match codec {
+        RAW => &blob[2..],
+        #[cfg(feature = "zstd")]
+        ZSTD => {
+            decompressed =
+                zstd::stream::decode_all(&blob[2..]).map_err(PackingError::Decompression)?;
+            &decompressed[..]
+        }
+        codec => return Err(PackingError::Codec(codec)),
+    };
+
+    let mut cursor = Cursor { bytes, position: 0 };
+    let count = cursor.varint()?;
+    let mut moves = Vec::with_capacity(count.min(bytes.len() as u64) as usize);
+    for num in 1..=count {
+        let descriptor = cursor.varint()?;
+        let descriptor = u32::try_from(descriptor)
+            .ok()
+            .and_then(|descriptor| MoveDescriptor::from_u32(descriptor).ok())
+            .ok_or(PackingError::Descriptor(descriptor as u32))?;
+        let [flags] = cursor.take()?;
+        let eval = if flags & EVAL_NUMERIC != 0 {
+            Some(Eval::Numeric(f32::from_le_bytes(cursor.take()?)))
+        } else if flags & EVAL_CHECKMATE != 0 {
+            Some(Eval::Checkmate(i8::from_le_bytes(cursor.take()?)))
+        } else {
+            None
+        };
+        let clk = if flags & CLOCK != 0 {
+            let seconds = cursor.varint()?;
+            let time = u32::try_from(seconds)
+                .ok()
+                .and_then(Time::from_seconds)
+                .ok_or(PackingError::Clock(seconds))?;
+            Some(Clk(time))
+        } else {
+            None
+        };
+        moves.push(Move {
+            num: num as usize,
+            descriptor,
+            eval,
+            clk,
+        });
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{Move, PackingError, pack, unpack};
+    use crate::attributes::{Clk, Eval, MoveDescriptor, datetime::Time};
+
+    /// Tests that the moves are unpacked as they were packed, and that a truncated blob is rejected.
+    #[test]
+    fn pack_test() {
+        let moves = (1..=40)
+            .map(|num| Move {
+                num,
+                descriptor: MoveDescriptor::default(),
+                eval: match num % 3 {
+                    0 => Some(Eval::Numeric(num as f32 / 10.0)),
+                    1 => Some(Eval::Checkmate(-(num as i8))),
+                    _ => None,
+                },
+                clk: (num % 2 == 0).then(|| Clk(Time::from_seconds(num as u32 * 61).unwrap())),
+            })
+            .collect::<Vec<_>>();
+        let blob = pack(&moves);
+        let unpacked = unpack(&blob).unwrap();
+        let describe = |moves: &[Move]| {
+            moves
+                .iter()
+                .map(|r#move| {
+                    format!(
+                        "{} {} {:?} {:?}",
+                        r#move.num,
+                        r#move.descriptor.to_u32(),
+                        r#move.eval.map(|eval| eval.to_string()),
+                        r#move.clk.map(|clk| clk.0.seconds())
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(describe(&unpacked), describe(&moves));
+        assert!(matches!(
+            unpack(&[super::VERSION, super::RAW, 1]),
+            Err(PackingError::Truncated(1))
+        ));
+        assert!(matches!(unpack(&[9, 0]), Err(PackingError::Version(9))));
+    }
+}
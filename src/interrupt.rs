@@ -0,0 +1,63 @@
+//! Handling of the interruptions of the scrapper, which stop the reading of new games so that the ones already read are finished and recorded.
+
+use std::{
+    process::exit,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use log::{error, trace, warn};
+
+/// Exit code of a run that was interrupted and stopped after finishing the games already read.
+pub const INTERRUPTED_EXIT_CODE: u8 = 130;
+
+/// Exit code of a run that was interrupted a second time, and thus exited immediately.
+const FORCED_EXIT_CODE: i32 = 131;
+
+/// Whether the run has been interrupted.
+#[cfg(not(test))]
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// Whether the test running in this thread has been interrupted, kept per thread so that the other tests are not.
+    static INTERRUPTED: AtomicBool = const { AtomicBool::new(false) };
+}
+
+/// Runs a function over the flag of the interruption.
+fn with_flag<T>(function: impl FnOnce(&AtomicBool) -> T) -> T {
+    #[cfg(not(test))]
+    {
+        function(&INTERRUPTED)
+    }
+    #[cfg(test)]
+    INTERRUPTED.with(function)
+}
+
+/// Handles the interruptions and terminations of the process, so that the first one stops the reading of new games and the second one exits immediately.
+///
+/// # Errors
+/// Will return [`ctrlc::Error`] if the handler could not be set.
+pub fn handle_interruptions() -> Result<(), ctrlc::Error> {
+    trace!("handle_interruptions function.");
+    ctrlc::set_handler(|| {
+        if with_flag(|flag| flag.swap(true, Ordering::SeqCst)) {
+            error!("Interrupted again, exiting immediately.");
+            log::logger().flush();
+            exit(FORCED_EXIT_CODE);
+        }
+        warn!(
+            "Interrupted, stopping after the games already read. Interrupt again to exit immediately."
+        );
+    })
+}
+
+/// Checks whether the run has been interrupted, in which case no more games must be read.
+pub fn interrupted() -> bool {
+    with_flag(|flag| flag.load(Ordering::SeqCst))
+}
+
+/// Interrupts the test running in this thread.
+#[cfg(test)]
+pub fn interrupt() {
+    with_flag(|flag| flag.store(true, Ordering::SeqCst));
+}
@@ -3,6 +3,7 @@
 pub mod attributes;
 pub mod constants;
 pub mod data;
+pub mod report;
 
 pub mod prelude {
     pub use super::attributes::*;
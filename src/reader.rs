@@ -18,9 +18,19 @@ use rand_seeder::{Seeder, SipRng};
 #[cfg(feature = "zstd")]
 use zstd::Decoder;
 
+use crate::interrupt::interrupted;
+
 /// Salt for the sampling.
 const SALT: &str = "ABCDEF";
 
+/// Gets the seed of the sampling of a file, which is its name along with the salt, so that the same file always gives the same sample.
+pub fn sample_seed<P: AsRef<Path>>(path: P) -> Option<String> {
+    path.as_ref()
+        .file_name()
+        .and_then(OsStr::to_str)
+        .map(|file| [file, SALT].concat())
+}
+
 /// The buffered reader used for a PGN or ZSTD compressed PGN file.
 pub enum PGNReader {
     /// A buffered reader for a simple PGN file.
@@ -71,24 +81,22 @@ impl PGNReader {
     ///
     /// # Errors
     /// Will return [`io::Error`] if the game could not be read.
-    #[allow(dead_code)]
     pub fn read_game<V: Visitor>(&mut self, visitor: &mut V) -> io::Result<Option<V::Result>> {
         match self {
             Self::PGN(file) => file.read_game(visitor),
+            #[cfg(feature = "zstd")]
             Self::ZST(file) => file.read_game(visitor),
         }
     }
 
-    /// Reads all the games from a PGN reader.
+    /// Reads all the games from a PGN reader, stopping before the next one if the run is interrupted.
     ///
     /// # Errors
     /// Will return [`io::Error`] if the games could not be read.
     pub fn read_all<V: Visitor>(&mut self, visitor: &mut V) -> io::Result<()> {
         trace!("PGNReader read_all function.");
-        match self {
-            Self::PGN(file) => file.read_all(visitor),
-            Self::ZST(file) => file.read_all(visitor),
-        }
+        while !interrupted() && self.read_game(visitor)?.is_some() {}
+        Ok(())
     }
 }
 
@@ -215,10 +223,10 @@ pub fn find_games<P: AsRef<Path> + Debug>(path: P) -> io::Result<usize> {
                 find_games_aux(Decoder::new(File::open(path)?)?)
             }
             #[cfg(not(feature = "zstd"))]
-            Err(Box::new(io::Error::new(
+            Err(io::Error::new(
                 io::ErrorKind::Unsupported,
                 "The feature zstd must be active to be able to read a zstd file",
-            )))
+            ))
         } else if ext == "pgn" {
             find_games_aux(File::open(path)?)
         } else {
@@ -235,12 +243,46 @@ pub fn find_games<P: AsRef<Path> + Debug>(path: P) -> io::Result<usize> {
     }
 }
 
-/// A sampler for a PGN file. Takes a sample of usizes and retrieves the data for making cursors for each of the games.
+/// Opens the PGN or ZSTD compressed PGN file at the given path as an unbuffered reader of its raw text.
+///
+/// # Errors
+/// Will return [`io::Error`] if the file could not be opened or if it's neither a PGN nor a ZSTD file.
+pub fn open_raw<P: AsRef<Path> + Debug>(path: P) -> io::Result<Box<dyn Read>> {
+    trace!("open_raw function.");
+    debug!("{path:?}");
+    if let Some(ext) = path.as_ref().extension() {
+        if ext == "zst" {
+            #[cfg(feature = "zstd")]
+            {
+                Ok(Box::new(Decoder::new(File::open(path)?)?))
+            }
+            #[cfg(not(feature = "zstd"))]
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "The feature zstd must be active to be able to read a zstd file",
+            ))
+        } else if ext == "pgn" {
+            Ok(Box::new(File::open(path)?))
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Only zstd and pgn files are allowed",
+            ))
+        }
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Only zstd and pgn files are allowed",
+        ))
+    }
+}
+
+/// A sampler for a PGN file. Takes a sample of usizes, or every game, and retrieves the data for making cursors for each of the games.
 pub struct PGNSampler<R: Read> {
     /// PGN file reader, with a buffer surrounding it.
     reader: BufReader<R>,
-    /// Sample of indices, which all must be valid.
-    sample: IntoIter<usize>,
+    /// Sample of indices, which all must be valid, or [`None`] if every game is to be retrieved.
+    sample: Option<IntoIter<usize>>,
     /// Counter for the scanned games.
     game_counter: usize,
     /// Counter for the processed games.
@@ -257,21 +299,16 @@ impl<R: Read> PGNSampler<R> {
         trace!("PGNSampler new function.");
         info!("Creating the sampler.");
         let mut indices = sample::<SipRng>(
-            &mut Seeder::from(
-                seed.file_name()
-                    .and_then(OsStr::to_str)
-                    .map(|file| [file, SALT].concat()),
-            )
-            .into_rng(),
+            &mut Seeder::from(sample_seed(seed)).into_rng(),
             total_games,
             sample_size,
         )
         .into_vec();
         indices.sort();
-        let divisor = sample_size >> 6;
+        let divisor = (sample_size >> 6).max(1);
         Self {
             reader: BufReader::with_capacity(1 << 15, reader),
-            sample: indices.into_iter(),
+            sample: Some(indices.into_iter()),
             game_counter: 0,
             processed_counter: 0,
             divisor,
@@ -279,87 +316,99 @@ impl<R: Read> PGNSampler<R> {
         }
     }
 
-    /// Fills the internal `current_data` with all the data for the immediate next game.
+    /// Constructs a new PGN sampler that retrieves every game of the file, buffering the reader.
+    pub fn all(reader: R) -> Self {
+        trace!("PGNSampler all function.");
+        info!("Creating the sampler for every game.");
+        Self {
+            reader: BufReader::with_capacity(1 << 15, reader),
+            sample: None,
+            game_counter: 0,
+            processed_counter: 0,
+            divisor: 0x100000,
+            current_data: Vec::new(),
+        }
+    }
+
+    /// Fills the internal `current_data` with all the data for the immediate next game, however many times the buffer has to be filled for it. Returns whether there was a game left, the last one possibly ending at the end of the file instead of an empty line.
     ///
     /// # Errors
-    /// Will return [`io::Error`] if the internal buffer could not be filled or if there is an error with the sampling.
-    fn fill_game(&mut self) -> io::Result<()> {
-        let mut buff;
-        let mut prev;
-        let mut len;
+    /// Will return [`io::Error`] if the internal buffer could not be filled.
+    fn fill_game(&mut self) -> io::Result<bool> {
+        // Whether the previous buffer ended in a line feed, to find the empty lines split between buffers.
+        let mut newline = false;
         self.current_data.clear();
 
-        buff = self.reader.fill_buf()?;
-        len = buff.len();
-        prev = len;
-        for double in memchr::memchr_iter(b'\n', buff) {
-            if prev + 1 == double {
-                self.game_counter += 1;
-
-                if self.game_counter & 1 == 0 {
-                    prev += 2;
-                    self.current_data.extend_from_slice(&buff[0..prev]);
-                    if self.game_counter & 0x7FFFFF == 0 {
-                        info!("Iterated over {} games.", self.game_counter >> 1);
-                    }
-                    self.reader.consume(prev);
-                    return Ok(());
+        loop {
+            let buff = self.reader.fill_buf()?;
+            if buff.is_empty() {
+                if self.current_data.iter().all(u8::is_ascii_whitespace) {
+                    self.current_data.clear();
+                    return Ok(false);
                 }
+                self.game_counter = (self.game_counter | 1) + 1;
+                return Ok(true);
             }
-            prev = double
-        }
-
-        self.current_data.extend_from_slice(buff);
-        self.reader.consume(len);
-
-        buff = self.reader.fill_buf()?;
-        if prev + 1 == len {
-            if let Some(first) = buff.first() {
-                if *first == b'\n' {
+            for double in memchr::memchr_iter(b'\n', buff) {
+                let empty = if double == 0 {
+                    newline
+                } else {
+                    buff[double - 1] == b'\n'
+                };
+                if empty {
                     self.game_counter += 1;
 
                     if self.game_counter & 1 == 0 {
-                        self.current_data.push(b'\n');
+                        self.current_data.extend_from_slice(&buff[..=double]);
                         if self.game_counter & 0x7FFFFF == 0 {
                             info!("Iterated over {} games.", self.game_counter >> 1);
                         }
-                        self.reader.consume(1);
-                        return Ok(());
+                        self.reader.consume(double + 1);
+                        return Ok(true);
                     }
                 }
             }
+            newline = buff.last() == Some(&b'\n');
+            let len = buff.len();
+            self.current_data.extend_from_slice(buff);
+            self.reader.consume(len);
         }
-        len = buff.len();
-        prev = len;
-
-        for double in memchr::memchr_iter(b'\n', buff) {
-            if prev + 1 == double {
-                self.game_counter += 1;
+    }
 
-                if self.game_counter & 1 == 0 {
-                    prev += 2;
-                    self.current_data.extend_from_slice(&buff[0..prev]);
-                    if self.game_counter & 0x7FFFFF == 0 {
-                        info!("Iterated over {} games.", self.game_counter >> 1);
-                    }
-                    self.reader.consume(prev);
-                    return Ok(());
-                }
-            }
-            prev = double
+    /// Fills the internal `current_data` with all the data for the game of the sample the reader is at, which must exist.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the internal buffer could not be filled or if the EOF is reached before the game.
+    fn fill_sampled_game(&mut self) -> io::Result<bool> {
+        if self.fill_game()? {
+            Ok(true)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "All the games to iterate over should exist, thus, there should be no EOF.",
+            ))
         }
-        Err(io::Error::new(
-            io::ErrorKind::UnexpectedEof,
-            "A full game should fit in the buffer, so there is no way to end up here.",
-        ))
     }
 
-    /// Fills the internal `current_data` with all the data for the next game to sample, unless there aren't any. Returns whether there is new data or not.
+    /// Fills the internal `current_data` with all the data for the next game to sample, unless there aren't any or the run is interrupted. Returns whether there is new data or not.
     ///
     /// # Errors
     /// Will return [`io::Error`] if the internal buffer could not be filled or if there is an error with the sampling and the EOF is reached prematurely.
     pub fn fill_next_game(&mut self) -> io::Result<bool> {
-        let current_game = if let Some(current_game) = self.sample.next() {
+        if interrupted() {
+            return Ok(false);
+        }
+        let Some(sample) = &mut self.sample else {
+            if !self.fill_game()? {
+                return Ok(false);
+            }
+            self.processed_counter += 1;
+            if self.processed_counter % self.divisor == 0 {
+                info!("Processed {} games.", self.processed_counter);
+            }
+            return Ok(true);
+        };
+        let current_game = if let Some(current_game) = sample.next() {
             self.processed_counter += 1;
             if self.processed_counter % self.divisor == 0 {
                 info!(
@@ -375,8 +424,7 @@ impl<R: Read> PGNSampler<R> {
         };
 
         if self.game_counter == current_game {
-            self.fill_game()?;
-            return Ok(true);
+            return self.fill_sampled_game();
         }
 
         let mut prev = 0;
@@ -394,8 +442,7 @@ impl<R: Read> PGNSampler<R> {
                         }
                         if self.game_counter == current_game {
                             self.reader.consume(1);
-                            self.fill_game()?;
-                            return Ok(true);
+                            return self.fill_sampled_game();
                         }
                     }
                 }
@@ -410,8 +457,7 @@ impl<R: Read> PGNSampler<R> {
                     }
                     if self.game_counter == current_game {
                         self.reader.consume(double + 1);
-                        self.fill_game()?;
-                        return Ok(true);
+                        return self.fill_sampled_game();
                     }
                 }
                 prev = double
@@ -427,3 +473,72 @@ impl<R: Read> PGNSampler<R> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::{remove_file, write},
+        io::Cursor,
+        path::PathBuf,
+    };
+
+    use pretty_assertions::assert_eq;
+
+    use super::{PGNReader, PGNSampler};
+    use crate::{interrupt::interrupt, visitors::checkcollect::checker::Checker};
+
+    /// Builds a game whose moves are followed by a comment of the length given.
+    fn game(site: &str, comment: usize) -> Vec<u8> {
+        format!(
+            "[Site \"{site}\"]\n[Result \"1-0\"]\n\n1. e4 {{ {} }} 1-0\n\n",
+            "x".repeat(comment)
+        )
+        .into_bytes()
+    }
+
+    /// Tests that the sampler retrieves games longer than its buffer and a last game without the empty line after it.
+    #[test]
+    fn fill_next_game_test() {
+        let long = game("long", 100_000);
+        let short = game("short", 10);
+        let last = game("last", 10);
+        let mut file = [long.as_slice(), &short, &last[..last.len() - 1]].concat();
+
+        let mut sampler = PGNSampler::all(Cursor::new(file.clone()));
+        assert!(sampler.fill_next_game().unwrap());
+        assert_eq!(sampler.current_data, long);
+        assert!(sampler.fill_next_game().unwrap());
+        assert_eq!(sampler.current_data, short);
+        assert!(sampler.fill_next_game().unwrap());
+        assert_eq!(sampler.current_data, &last[..last.len() - 1]);
+        assert!(!sampler.fill_next_game().unwrap());
+
+        file.push(b'\n');
+        let mut sampler = PGNSampler::new(Cursor::new(file), 2, 3, &PathBuf::from("test.pgn"));
+        let mut games = 0;
+        while sampler.fill_next_game().unwrap() {
+            assert!([&long, &short, &last].contains(&&sampler.current_data));
+            games += 1;
+        }
+        assert_eq!(games, 2);
+    }
+
+    /// Tests that the readers stop before the next game once the run is interrupted.
+    #[test]
+    fn interrupted_test() {
+        let file = [game("first", 10), game("second", 10)].concat();
+        let path = std::env::temp_dir().join("lichess-interrupt-test.pgn");
+        write(&path, &file).unwrap();
+        let mut pgn = PGNReader::new(&path).unwrap();
+        let mut checker = Checker::default();
+        let mut sampler = PGNSampler::all(Cursor::new(file));
+        assert!(pgn.read_game(&mut checker).unwrap().is_some());
+        assert!(sampler.fill_next_game().unwrap());
+
+        interrupt();
+        pgn.read_all(&mut checker).unwrap();
+        assert_eq!(checker.games, 1);
+        assert!(!sampler.fill_next_game().unwrap());
+        remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+//! Structured, machine-readable reports of the errors found while processing the games of a PGN file. Each error is written as a JSON line, followed by a summary of the counts per category.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use log::{info, trace};
+
+use crate::attributes::{
+    AttributeKind,
+    error::{AttributeParsingError, ValuedAttributeParsingError},
+};
+
+/// All possible [`ErrorCategory`]s, in the order they are summarized.
+pub const ALL_CATEGORIES: [ErrorCategory; 8] = [
+    ErrorCategory::InvalidValue,
+    ErrorCategory::NullHeader,
+    ErrorCategory::Inconsistency,
+    ErrorCategory::UnknownHeader,
+    ErrorCategory::UnknownComment,
+    ErrorCategory::InvalidSan,
+    ErrorCategory::InvalidUtf8,
+    ErrorCategory::Insertion,
+];
+
+/// The category of an error found while processing a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// A value that could not be parsed as its attribute.
+    InvalidValue,
+    /// A header that should be present but is not.
+    NullHeader,
+    /// Two parts of a game that do not agree with each other.
+    Inconsistency,
+    /// A header that is not known.
+    UnknownHeader,
+    /// A comment that is not known.
+    UnknownComment,
+    /// A SAN move that is not valid in its position.
+    InvalidSan,
+    /// A value that is not valid UTF-8.
+    InvalidUtf8,
+    /// An error while inserting the game into the database.
+    Insertion,
+}
+
+impl ErrorCategory {
+    /// Retrieves the representation of this [`ErrorCategory`] as a `&'static str`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidValue => "invalid_value",
+            Self::NullHeader => "null_header",
+            Self::Inconsistency => "inconsistency",
+            Self::UnknownHeader => "unknown_header",
+            Self::UnknownComment => "unknown_comment",
+            Self::InvalidSan => "invalid_san",
+            Self::InvalidUtf8 => "invalid_utf8",
+            Self::Insertion => "insertion",
+        }
+    }
+
+    /// Retrieves the position of this [`ErrorCategory`] in [`ALL_CATEGORIES`].
+    const fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// A single problem found while processing a game.
+#[derive(Debug, Clone)]
+pub struct ErrorRecord {
+    /// Index of the game in the file.
+    pub game: usize,
+    /// The header or comment key the error happened at, if any.
+    pub key: Option<String>,
+    /// The kind of attribute that was being parsed, if any.
+    pub kind: Option<AttributeKind>,
+    /// The raw value that caused the error, if any.
+    pub value: Option<String>,
+    /// The category of the error.
+    pub category: ErrorCategory,
+    /// Human readable description of the error.
+    pub message: String,
+}
+
+impl ErrorRecord {
+    /// Creates a new record for the game, without key, kind nor value.
+    pub fn new<M: ToString>(game: usize, category: ErrorCategory, message: M) -> Self {
+        Self {
+            game,
+            key: None,
+            kind: None,
+            value: None,
+            category,
+            message: message.to_string(),
+        }
+    }
+
+    /// Sets the header or comment key of the record.
+    pub fn with_key(mut self, key: &[u8]) -> Self {
+        self.key = Some(String::from_utf8_lossy(key).into_owned());
+        self
+    }
+
+    /// Sets the raw value of the record.
+    pub fn with_value(mut self, value: &[u8]) -> Self {
+        self.value = Some(String::from_utf8_lossy(value).into_owned());
+        self
+    }
+
+    /// Writes the record as a single JSON line, with the Lichess ID of its game if known.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn write_json<W: Write>(&self, writer: &mut W, lichess_id: Option<&str>) -> io::Result<()> {
+        write!(writer, "{{\"game\":{},\"lichess_id\":", self.game)?;
+        write_json_option(writer, lichess_id)?;
+        write!(writer, ",\"key\":")?;
+        write_json_option(writer, self.key.as_deref())?;
+        write!(writer, ",\"kind\":")?;
+        write_json_option(writer, self.kind.as_ref().map(AttributeKind::name))?;
+        write!(writer, ",\"value\":")?;
+        write_json_option(writer, self.value.as_deref())?;
+        write!(writer, ",\"category\":\"{}\",\"message\":", self.category)?;
+        write_json_str(writer, &self.message)?;
+        writeln!(writer, "}}")
+    }
+}
+
+impl Display for ErrorRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.category.fmt(f)?;
+        if let Some(key) = &self.key {
+            write!(f, " {key}")?;
+        }
+        match &self.value {
+            Some(value) => write!(f, " = {value}"),
+            None => write!(f, ": {}", self.message),
+        }
+    }
+}
+
+/// Conversion of an error into an [`ErrorRecord`] of the game it happened at.
+pub trait ToErrorRecord {
+    /// Creates a new record of the game from this error.
+    fn to_record(&self, game: usize) -> ErrorRecord;
+}
+
+impl ToErrorRecord for AttributeParsingError {
+    fn to_record(&self, game: usize) -> ErrorRecord {
+        ErrorRecord {
+            kind: Some(self.kind()),
+            ..ErrorRecord::new(game, ErrorCategory::InvalidValue, self)
+        }
+    }
+}
+
+impl ToErrorRecord for ValuedAttributeParsingError {
+    fn to_record(&self, game: usize) -> ErrorRecord {
+        ErrorRecord {
+            kind: Some(self.kind()),
+            ..ErrorRecord::new(game, ErrorCategory::InvalidValue, self).with_value(self.value())
+        }
+    }
+}
+
+/// Writes a string slice as a JSON string, escaping it as needed.
+///
+/// # Errors
+/// Will return [`io::Error`] if the writing fails.
+pub fn write_json_str<W: Write>(writer: &mut W, value: &str) -> io::Result<()> {
+    write!(writer, "\"")?;
+    for char in value.chars() {
+        match char {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            char if char.is_control() => write!(writer, "\\u{:04x}", char as u32)?,
+            char => write!(writer, "{char}")?,
+        }
+    }
+    write!(writer, "\"")
+}
+
+/// Writes an optional string slice as a JSON string, or `null` if there is none.
+///
+/// # Errors
+/// Will return [`io::Error`] if the writing fails.
+fn write_json_option<W: Write>(writer: &mut W, value: Option<&str>) -> io::Result<()> {
+    match value {
+        Some(value) => write_json_str(writer, value),
+        None => write!(writer, "null"),
+    }
+}
+
+/// Collector of the errors of the games being processed, that keeps the count for each category and optionally writes each of them to a JSON lines file.
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    /// The file the records are written to, if any.
+    writer: Option<BufWriter<File>>,
+    /// Whether only [`ErrorCategory::Insertion`] records must be collected, used when another pass already collected the rest.
+    insertions_only: bool,
+    /// The Lichess ID of the current game, if known.
+    lichess_id: Option<String>,
+    /// Records of the current game.
+    pending: Vec<ErrorRecord>,
+    /// Records of the last game that ended.
+    last: Vec<ErrorRecord>,
+    /// Number of records per category, indexed as [`ALL_CATEGORIES`].
+    counts: [usize; ALL_CATEGORIES.len()],
+}
+
+impl ErrorReport {
+    /// Creates a new report that writes its records to the file in the given path.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the file could not be created.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        trace!("ErrorReport create function.");
+        Ok(Self {
+            writer: Some(BufWriter::new(File::create(path)?)),
+            ..Default::default()
+        })
+    }
+
+    /// Hands the report over to another pass, leaving an empty one in its place. The pass it is handed to collects either only [`ErrorCategory::Insertion`] records or all of them.
+    pub fn hand_over(&mut self, insertions_only: bool) -> Self {
+        let mut report = std::mem::take(self);
+        report.insertions_only = insertions_only;
+        report
+    }
+
+    /// Sets the Lichess ID of the current game from its `Site` or `LichessId` header.
+    pub fn set_lichess_id(&mut self, value: &[u8]) {
+        let id = value.rsplit(|char| *char == b'/').next().unwrap_or(value);
+        if !id.is_empty() {
+            self.lichess_id = Some(String::from_utf8_lossy(id).into_owned());
+        }
+    }
+
+    /// Adds a record to the current game.
+    pub fn push(&mut self, record: ErrorRecord) {
+        if self.insertions_only & (record.category != ErrorCategory::Insertion) {
+            return;
+        }
+        self.counts[record.category.index()] += 1;
+        self.pending.push(record);
+    }
+
+    /// Records of the current game, valid until [`ErrorReport::end_game`] is called.
+    pub fn game_errors(&self) -> &[ErrorRecord] {
+        &self.pending
+    }
+
+    /// Records of the last game that ended, valid until [`ErrorReport::end_game`] is called again.
+    pub fn last_game_errors(&self) -> &[ErrorRecord] {
+        &self.last
+    }
+
+    /// Writes the records of the current game, if there is a file to write them to, and starts a new game.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn end_game(&mut self) -> io::Result<()> {
+        let result = if let Some(writer) = &mut self.writer {
+            self.pending
+                .iter()
+                .try_for_each(|record| record.write_json(writer, self.lichess_id.as_deref()))
+        } else {
+            Ok(())
+        };
+        self.last.clear();
+        std::mem::swap(&mut self.pending, &mut self.last);
+        self.lichess_id = None;
+        result
+    }
+
+    /// Number of records collected for each category.
+    pub fn counts(&self) -> impl Iterator<Item = (ErrorCategory, usize)> + '_ {
+        ALL_CATEGORIES.into_iter().zip(self.counts)
+    }
+
+    /// Total number of records collected.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Logs the table of counts per category.
+    pub fn log_summary(&self) {
+        info!("Error report summary");
+        for (category, count) in self.counts() {
+            info!("{category:>15}: {count}");
+        }
+        info!("{:>15}: {}", "total", self.total());
+    }
+
+    /// Writes the summary of counts per category as the last JSON line and flushes the file, if there is one.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writing fails.
+    pub fn finish(&mut self) -> io::Result<()> {
+        trace!("ErrorReport finish function.");
+        self.end_game()?;
+        let Some(writer) = &mut self.writer else {
+            return Ok(());
+        };
+        write!(writer, "{{\"summary\":{{")?;
+        for (category, count) in ALL_CATEGORIES.into_iter().zip(self.counts) {
+            write!(writer, "\"{category}\":{count},")?;
+        }
+        writeln!(
+            writer,
+            "\"total\":{}}}}}",
+            self.counts.iter().sum::<usize>()
+        )?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{ErrorCategory, ErrorRecord, ToErrorRecord};
+    use crate::attributes::TimeControl;
+
+    /// Tests whether the JSON lines of the records are well formed or not.
+    #[test]
+    pub fn record_json_test() {
+        let error = TimeControl::try_from(b"300+x".as_slice()).unwrap_err();
+        let mut buffer = Vec::new();
+        error
+            .to_record(7)
+            .with_key(b"TimeControl")
+            .write_json(&mut buffer, Some("AbCdEfGh"))
+            .unwrap();
+        ErrorRecord::new(8, ErrorCategory::NullHeader, "Site is \"null\"")
+            .write_json(&mut buffer, None)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            concat!(
+                "{\"game\":7,\"lichess_id\":\"AbCdEfGh\",\"key\":\"TimeControl\",\"kind\":\"TimeControl\",\"value\":\"300+x\",\"category\":\"invalid_value\",\"message\":\"unable to parse `time control` as it's not formatted correctly (<-|{u16}+{u8}>): 300+x <- [51, 48, 48, 43, 120]\"}\n",
+                "{\"game\":8,\"lichess_id\":null,\"key\":null,\"kind\":null,\"value\":null,\"category\":\"null_header\",\"message\":\"Site is \\\"null\\\"\"}\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,10 @@
+ALTER TABLE Move
+ADD PRIMARY KEY (GameId, Num),
+ADD FOREIGN KEY (GameId) REFERENCES Game(GameId);
+ALTER TABLE Game
+ADD FOREIGN KEY (RuleSetId) REFERENCES RuleSet(RuleSetId),
+ADD FOREIGN KEY (OpeningId) REFERENCES Opening(OpeningId),
+ADD FOREIGN KEY (FCId) REFERENCES FinalConfiguration(FCId),
+ADD FOREIGN KEY (White) REFERENCES Player(PlayerId),
+ADD FOREIGN KEY (Black) REFERENCES Player(PlayerId),
+ADD FOREIGN KEY (RunId) REFERENCES IngestionRun(RunId);
@@ -0,0 +1,6 @@
+INSERT INTO EndgameStats (Signature, EloBucket, Result, Games)
+SELECT CONCAT('K', SUBSTR('QQQQQQQ', 1, p.EndWhiteQueens), SUBSTR('RRRRRRR', 1, p.EndWhiteRooks), SUBSTR('BBBBBBB', 1, p.EndWhiteBishops), SUBSTR('NNNNNNN', 1, p.EndWhiteKnights), SUBSTR('PPPPPPPP', 1, p.EndWhitePawns), 'vK', SUBSTR('QQQQQQQ', 1, p.EndBlackQueens), SUBSTR('RRRRRRR', 1, p.EndBlackRooks), SUBSTR('BBBBBBB', 1, p.EndBlackBishops), SUBSTR('NNNNNNN', 1, p.EndBlackKnights), SUBSTR('PPPPPPPP', 1, p.EndBlackPawns)), COALESCE((g.WhiteElo + g.BlackElo) DIV 200 * 100, 0), COALESCE(g.Result, 'Null'), COUNT(*)
+FROM Game g JOIN PiecesLeft p ON g.FCId = p.FCId
+WHERE g.GameId BETWEEN ? AND ?
+GROUP BY 1, 2, 3
+ON DUPLICATE KEY UPDATE Games = Games + VALUES(Games);
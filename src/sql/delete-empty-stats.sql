@@ -0,0 +1,2 @@
+DELETE FROM OpeningStats WHERE Games = 0;
+DELETE FROM EndgameStats WHERE Games = 0;
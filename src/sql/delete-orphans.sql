@@ -0,0 +1,4 @@
+DELETE FROM Player WHERE NOT EXISTS (SELECT 1 FROM Game WHERE Game.White = Player.PlayerId) AND NOT EXISTS (SELECT 1 FROM Game WHERE Game.Black = Player.PlayerId);
+DELETE FROM Opening WHERE NOT EXISTS (SELECT 1 FROM Game WHERE Game.OpeningId = Opening.OpeningId);
+DELETE FROM RuleSet WHERE NOT EXISTS (SELECT 1 FROM Game WHERE Game.RuleSetId = RuleSet.RuleSetId);
+DELETE FROM FinalConfiguration WHERE NOT EXISTS (SELECT 1 FROM Game WHERE Game.FCId = FinalConfiguration.FCId);
@@ -0,0 +1 @@
+DELETE FROM Move WHERE GameId IN (SELECT GameId FROM Game WHERE RunId = :run_id);
@@ -0,0 +1,2 @@
+INSERT INTO Game (RuleSetId, OpeningId, FCId, White, WhiteElo, WhiteTitle, Black, BlackElo, BlackTitle, StartTime, Increment, Result, Termination, DateTime, HasClock, HasEvaluations, RunId)
+VALUES
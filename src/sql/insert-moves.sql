@@ -0,0 +1,2 @@
+INSERT INTO Move (GameId, Num, Descriptor, EvalFloat, EvalInt, Clock)
+VALUES
@@ -0,0 +1,2 @@
+INSERT INTO IngestionRun (Version, Features, Arguments, SampleSeed, SampleSize, SourceFile)
+VALUES (:version, :features, :arguments, :sample_seed, :sample_size, :source_file);
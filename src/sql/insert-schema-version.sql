@@ -0,0 +1,2 @@
+INSERT INTO SchemaVersion (Version)
+VALUES (:version);
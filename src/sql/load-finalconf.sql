@@ -0,0 +1,6 @@
+LOAD DATA LOCAL INFILE ':file' INTO TABLE FinalConfiguration
+CHARACTER SET utf8mb4
+FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '"' ESCAPED BY ''
+LINES TERMINATED BY '\n'
+IGNORE 1 LINES
+(FCId, EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow);
@@ -0,0 +1,7 @@
+LOAD DATA LOCAL INFILE ':file' INTO TABLE Game
+CHARACTER SET utf8mb4
+FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '"' ESCAPED BY ''
+LINES TERMINATED BY '\n'
+IGNORE 1 LINES
+(GameId, RuleSetId, @OpeningId, FCId, @White, @WhiteElo, @WhiteTitle, @Black, @BlackElo, @BlackTitle, @StartTime, @Increment, Result, Termination, DateTime, HasClock, HasEvaluations)
+SET OpeningId = NULLIF(@OpeningId, ''), White = NULLIF(@White, ''), WhiteElo = NULLIF(@WhiteElo, ''), WhiteTitle = NULLIF(@WhiteTitle, ''), Black = NULLIF(@Black, ''), BlackElo = NULLIF(@BlackElo, ''), BlackTitle = NULLIF(@BlackTitle, ''), StartTime = NULLIF(@StartTime, ''), Increment = NULLIF(@Increment, '');
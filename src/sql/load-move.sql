@@ -0,0 +1,7 @@
+LOAD DATA LOCAL INFILE ':file' INTO TABLE Move
+CHARACTER SET utf8mb4
+FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '"' ESCAPED BY ''
+LINES TERMINATED BY '\n'
+IGNORE 1 LINES
+(GameId, Num, Descriptor, @EvalFloat, @EvalInt, @Clock)
+SET EvalFloat = NULLIF(@EvalFloat, ''), EvalInt = NULLIF(@EvalInt, ''), Clock = NULLIF(@Clock, '');
@@ -0,0 +1,6 @@
+LOAD DATA LOCAL INFILE ':file' INTO TABLE Player
+CHARACTER SET utf8mb4
+FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '"' ESCAPED BY ''
+LINES TERMINATED BY '\n'
+IGNORE 1 LINES
+(PlayerId, Name);
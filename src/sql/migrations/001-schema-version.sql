@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS SchemaVersion (
+Version INT UNSIGNED PRIMARY KEY NOT NULL,
+AppliedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS IngestionRun (
+RunId INT PRIMARY KEY NOT NULL AUTO_INCREMENT,
+Version VARCHAR(32) NOT NULL,
+Features VARCHAR(512) NOT NULL,
+Arguments TEXT NOT NULL,
+SampleSeed VARCHAR(255) NULL,
+SampleSize INT UNSIGNED NULL,
+GamesParsed INT UNSIGNED NOT NULL DEFAULT 0,
+GamesInserted INT UNSIGNED NOT NULL DEFAULT 0,
+GamesRejected INT UNSIGNED NOT NULL DEFAULT 0,
+GamesWithErrors INT UNSIGNED NOT NULL DEFAULT 0,
+StartedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+FinishedAt DATETIME NULL
+);
+ALTER TABLE Game ADD COLUMN RunId INT NULL, ADD FOREIGN KEY (RunId) REFERENCES IngestionRun(RunId);
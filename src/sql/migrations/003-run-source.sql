@@ -0,0 +1 @@
+ALTER TABLE IngestionRun ADD COLUMN SourceFile VARCHAR(255) NULL, ADD COLUMN PurgedAt DATETIME NULL;
@@ -0,0 +1,2 @@
+COPY Move (GameId, Num, Descriptor, EvalFloat, EvalInt, Clock)
+FROM STDIN (FORMAT BINARY);
@@ -0,0 +1,8 @@
+DROP SCHEMA IF EXISTS lichess CASCADE;
+CREATE SCHEMA IF NOT EXISTS lichess;
+SET search_path TO lichess;
+CREATE TYPE Title AS ENUM ('BOT', 'LM', 'GM', 'WGM', 'IM', 'WIM', 'FM', 'WFM', 'CM', 'WCM', 'NM', 'WNM', 'GR', 'MC', 'MN', 'M');
+CREATE TYPE GameResult AS ENUM ('Null', 'White', 'Black', 'Tie');
+CREATE TYPE Termination AS ENUM ('Unterminated', 'Normal', 'TimeForfeit', 'RulesInfraction', 'Abandoned');
+CREATE TYPE RuleSetKind AS ENUM ('Arena', 'Swiss', 'Game');
+CREATE TYPE EcoLetter AS ENUM ('Q', 'A', 'B', 'C', 'D', 'E');
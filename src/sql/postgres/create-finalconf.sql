@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS FinalConfiguration CASCADE;
+CREATE TABLE IF NOT EXISTS FinalConfiguration (
+FCId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+EndBlackPieces INT NOT NULL CHECK (EndBlackPieces >= 0),
+EndWhitePieces INT NOT NULL CHECK (EndWhitePieces >= 0),
+ARow BIGINT NOT NULL CHECK (ARow >= 0),
+BRow BIGINT NOT NULL CHECK (BRow >= 0),
+CRow BIGINT NOT NULL CHECK (CRow >= 0),
+DRow BIGINT NOT NULL CHECK (DRow >= 0),
+ERow BIGINT NOT NULL CHECK (ERow >= 0),
+FRow BIGINT NOT NULL CHECK (FRow >= 0),
+GRow BIGINT NOT NULL CHECK (GRow >= 0),
+HRow BIGINT NOT NULL CHECK (HRow >= 0),
+CONSTRAINT UC_FinalConfiguration UNIQUE (EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow)
+);
@@ -0,0 +1,25 @@
+DROP TABLE IF EXISTS Game CASCADE;
+CREATE TABLE IF NOT EXISTS Game (
+GameId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+RuleSetId INT NOT NULL,
+OpeningId INT NULL,
+FCId INT NOT NULL,
+White INT NULL,
+WhiteElo INT NULL CHECK (WhiteElo >= 0),
+WhiteTitle Title NULL,
+Black INT NULL,
+BlackElo INT NULL CHECK (BlackElo >= 0),
+BlackTitle Title NULL,
+StartTime INT NULL CHECK (StartTime >= 0),
+Increment SMALLINT NULL CHECK (Increment >= 0),
+Result GameResult,
+Termination Termination,
+DateTime TIMESTAMP,
+HasClock BOOLEAN,
+HasEvaluations BOOLEAN,
+FOREIGN KEY (RuleSetId) REFERENCES RuleSet(RuleSetId),
+FOREIGN KEY (OpeningId) REFERENCES Opening(OpeningId),
+FOREIGN KEY (FCId) REFERENCES FinalConfiguration(FCId),
+FOREIGN KEY (White) REFERENCES Player(PlayerId),
+FOREIGN KEY (Black) REFERENCES Player(PlayerId)
+);
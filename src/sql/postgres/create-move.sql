@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS Move CASCADE;
+CREATE TABLE IF NOT EXISTS Move (
+GameId INT NOT NULL,
+Num INT NOT NULL CHECK (Num >= 0),
+Descriptor BIGINT NOT NULL CHECK (Descriptor >= 0),
+EvalFloat REAL NULL,
+EvalInt SMALLINT NULL,
+Clock TIME NULL,
+PRIMARY KEY (GameId, Num),
+FOREIGN KEY (GameId) REFERENCES Game(GameId)
+);
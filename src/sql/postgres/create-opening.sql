@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS Opening CASCADE;
+CREATE TABLE IF NOT EXISTS Opening (
+OpeningId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+Name VARCHAR(100) NOT NULL,
+EcoLetter EcoLetter NOT NULL,
+EcoNumber SMALLINT NOT NULL CHECK (EcoNumber BETWEEN 0 AND 99),
+UNIQUE (Name)
+);
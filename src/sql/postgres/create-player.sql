@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS Player CASCADE;
+CREATE TABLE IF NOT EXISTS Player (
+PlayerId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+Name VARCHAR(31) NOT NULL,
+UNIQUE (Name)
+);
@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS RuleSet CASCADE;
+CREATE TABLE IF NOT EXISTS RuleSet (
+RuleSetId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+Name VARCHAR(31) NOT NULL,
+URLId VARCHAR(8) NOT NULL,
+Kind RuleSetKind NOT NULL,
+CONSTRAINT UC_RuleSet UNIQUE (Name, URLId)
+);
@@ -0,0 +1 @@
+DELETE FROM Game WHERE RunId = $1;
@@ -0,0 +1,4 @@
+INSERT INTO FinalConfiguration (EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+ON CONFLICT ON CONSTRAINT UC_FinalConfiguration DO UPDATE SET EndBlackPieces = EXCLUDED.EndBlackPieces
+RETURNING FCId;
@@ -0,0 +1,3 @@
+INSERT INTO Game (RuleSetId, OpeningId, FCId, White, WhiteElo, WhiteTitle, Black, BlackElo, BlackTitle, StartTime, Increment, Result, Termination, DateTime, HasClock, HasEvaluations, RunId)
+VALUES ($1, $2, $3, $4, $5, $6::TEXT::Title, $7, $8, $9::TEXT::Title, $10, $11, $12::TEXT::GameResult, $13::TEXT::Termination, $14, $15, $16, $17)
+RETURNING GameId;
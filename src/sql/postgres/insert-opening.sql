@@ -0,0 +1,4 @@
+INSERT INTO Opening (Name, EcoLetter, EcoNumber)
+VALUES ($1, $2::TEXT::EcoLetter, $3)
+ON CONFLICT (Name) DO UPDATE SET Name = EXCLUDED.Name
+RETURNING OpeningId;
@@ -0,0 +1,4 @@
+INSERT INTO Player (Name)
+VALUES ($1)
+ON CONFLICT (Name) DO UPDATE SET Name = EXCLUDED.Name
+RETURNING PlayerId;
@@ -0,0 +1,4 @@
+INSERT INTO RuleSet (Name, URLId, Kind)
+VALUES ($1, $2, $3::TEXT::RuleSetKind)
+ON CONFLICT ON CONSTRAINT UC_RuleSet DO UPDATE SET Name = EXCLUDED.Name
+RETURNING RuleSetId;
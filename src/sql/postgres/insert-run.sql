@@ -0,0 +1,3 @@
+INSERT INTO IngestionRun (Version, Features, Arguments, SampleSeed, SampleSize, SourceFile)
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING RunId;
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS SchemaVersion (
+Version INT PRIMARY KEY CHECK (Version > 0),
+AppliedAt TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
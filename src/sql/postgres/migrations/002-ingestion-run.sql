@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS IngestionRun (
+RunId INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+Version TEXT NOT NULL,
+Features TEXT NOT NULL,
+Arguments TEXT NOT NULL,
+SampleSeed TEXT NULL,
+SampleSize BIGINT NULL CHECK (SampleSize >= 0),
+GamesParsed BIGINT NOT NULL DEFAULT 0 CHECK (GamesParsed >= 0),
+GamesInserted BIGINT NOT NULL DEFAULT 0 CHECK (GamesInserted >= 0),
+GamesRejected BIGINT NOT NULL DEFAULT 0 CHECK (GamesRejected >= 0),
+GamesWithErrors BIGINT NOT NULL DEFAULT 0 CHECK (GamesWithErrors >= 0),
+StartedAt TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+FinishedAt TIMESTAMP NULL
+);
+ALTER TABLE Game ADD COLUMN RunId INT NULL REFERENCES IngestionRun(RunId);
@@ -0,0 +1 @@
+ALTER TABLE IngestionRun ADD COLUMN SourceFile TEXT NULL, ADD COLUMN PurgedAt TIMESTAMP NULL;
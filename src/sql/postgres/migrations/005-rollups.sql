@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS OpeningStats (
+Month CHAR(7) NOT NULL,
+OpeningFamily VARCHAR(100) NOT NULL,
+Speed VARCHAR(14) NOT NULL CHECK (Speed IN ('UltraBullet', 'Bullet', 'Blitz', 'Rapid', 'Classical', 'Correspondence')),
+EloBucket INT NOT NULL CHECK (EloBucket >= 0),
+Games BIGINT NOT NULL,
+WhiteWins BIGINT NOT NULL,
+Draws BIGINT NOT NULL,
+BlackWins BIGINT NOT NULL,
+PRIMARY KEY (Month, OpeningFamily, Speed, EloBucket)
+);
+CREATE TABLE IF NOT EXISTS EndgameStats (
+Signature VARCHAR(80) NOT NULL,
+EloBucket INT NOT NULL CHECK (EloBucket >= 0),
+Result GameResult NOT NULL,
+Games BIGINT NOT NULL,
+PRIMARY KEY (Signature, EloBucket, Result)
+);
+INSERT INTO OpeningStats (Month, OpeningFamily, Speed, EloBucket, Games, WhiteWins, Draws, BlackWins)
+SELECT COALESCE(TO_CHAR(g.DateTime, 'YYYY-MM'), ''), COALESCE(SPLIT_PART(o.Name, ':', 1), ''), CASE WHEN g.StartTime IS NULL THEN 'Correspondence' WHEN g.StartTime + 40 * g.Increment < 30 THEN 'UltraBullet' WHEN g.StartTime + 40 * g.Increment < 180 THEN 'Bullet' WHEN g.StartTime + 40 * g.Increment < 480 THEN 'Blitz' WHEN g.StartTime + 40 * g.Increment < 1500 THEN 'Rapid' ELSE 'Classical' END, COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), COUNT(*), SUM(CASE WHEN g.Result = 'White' THEN 1 ELSE 0 END), SUM(CASE WHEN g.Result = 'Tie' THEN 1 ELSE 0 END), SUM(CASE WHEN g.Result = 'Black' THEN 1 ELSE 0 END)
+FROM Game g LEFT JOIN Opening o ON g.OpeningId = o.OpeningId
+GROUP BY 1, 2, 3, 4;
+INSERT INTO EndgameStats (Signature, EloBucket, Result, Games)
+SELECT 'K' || SUBSTR('QQQQQQQ', 1, p.EndWhiteQueens) || SUBSTR('RRRRRRR', 1, p.EndWhiteRooks) || SUBSTR('BBBBBBB', 1, p.EndWhiteBishops) || SUBSTR('NNNNNNN', 1, p.EndWhiteKnights) || SUBSTR('PPPPPPPP', 1, p.EndWhitePawns) || 'vK' || SUBSTR('QQQQQQQ', 1, p.EndBlackQueens) || SUBSTR('RRRRRRR', 1, p.EndBlackRooks) || SUBSTR('BBBBBBB', 1, p.EndBlackBishops) || SUBSTR('NNNNNNN', 1, p.EndBlackKnights) || SUBSTR('PPPPPPPP', 1, p.EndBlackPawns), COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), COALESCE(g.Result, 'Null'), COUNT(*)
+FROM Game g JOIN PiecesLeft p ON g.FCId = p.FCId
+GROUP BY 1, 2, 3;
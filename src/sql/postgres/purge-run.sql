@@ -0,0 +1 @@
+UPDATE IngestionRun SET PurgedAt = CURRENT_TIMESTAMP WHERE RunId = $1;
@@ -0,0 +1,6 @@
+SELECT t.relname, c.conname
+FROM pg_constraint c
+JOIN pg_class t ON c.conrelid = t.oid
+JOIN pg_namespace s ON t.relnamespace = s.oid
+WHERE s.nspname = $1 AND ((t.relname = $2 AND c.contype = 'f') OR (t.relname = $3 AND c.contype IN ('f', 'p')))
+ORDER BY c.contype = 'p';
@@ -0,0 +1 @@
+SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = $1 AND table_name = $2;
@@ -0,0 +1 @@
+SELECT RunId FROM IngestionRun WHERE SourceFile = $1 AND PurgedAt IS NULL ORDER BY RunId;
@@ -0,0 +1,6 @@
+INSERT INTO EndgameStats (Signature, EloBucket, Result, Games)
+SELECT 'K' || SUBSTR('QQQQQQQ', 1, p.EndWhiteQueens) || SUBSTR('RRRRRRR', 1, p.EndWhiteRooks) || SUBSTR('BBBBBBB', 1, p.EndWhiteBishops) || SUBSTR('NNNNNNN', 1, p.EndWhiteKnights) || SUBSTR('PPPPPPPP', 1, p.EndWhitePawns) || 'vK' || SUBSTR('QQQQQQQ', 1, p.EndBlackQueens) || SUBSTR('RRRRRRR', 1, p.EndBlackRooks) || SUBSTR('BBBBBBB', 1, p.EndBlackBishops) || SUBSTR('NNNNNNN', 1, p.EndBlackKnights) || SUBSTR('PPPPPPPP', 1, p.EndBlackPawns), COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), COALESCE(g.Result, 'Null'), -COUNT(*)
+FROM Game g JOIN PiecesLeft p ON g.FCId = p.FCId
+WHERE g.RunId = $1
+GROUP BY 1, 2, 3
+ON CONFLICT (Signature, EloBucket, Result) DO UPDATE SET Games = EndgameStats.Games + excluded.Games;
@@ -0,0 +1,6 @@
+INSERT INTO OpeningStats (Month, OpeningFamily, Speed, EloBucket, Games, WhiteWins, Draws, BlackWins)
+SELECT COALESCE(TO_CHAR(g.DateTime, 'YYYY-MM'), ''), COALESCE(SPLIT_PART(o.Name, ':', 1), ''), CASE WHEN g.StartTime IS NULL THEN 'Correspondence' WHEN g.StartTime + 40 * g.Increment < 30 THEN 'UltraBullet' WHEN g.StartTime + 40 * g.Increment < 180 THEN 'Bullet' WHEN g.StartTime + 40 * g.Increment < 480 THEN 'Blitz' WHEN g.StartTime + 40 * g.Increment < 1500 THEN 'Rapid' ELSE 'Classical' END, COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), -COUNT(*), -SUM(CASE WHEN g.Result = 'White' THEN 1 ELSE 0 END), -SUM(CASE WHEN g.Result = 'Tie' THEN 1 ELSE 0 END), -SUM(CASE WHEN g.Result = 'Black' THEN 1 ELSE 0 END)
+FROM Game g LEFT JOIN Opening o ON g.OpeningId = o.OpeningId
+WHERE g.RunId = $1
+GROUP BY 1, 2, 3, 4
+ON CONFLICT (Month, OpeningFamily, Speed, EloBucket) DO UPDATE SET Games = OpeningStats.Games + excluded.Games, WhiteWins = OpeningStats.WhiteWins + excluded.WhiteWins, Draws = OpeningStats.Draws + excluded.Draws, BlackWins = OpeningStats.BlackWins + excluded.BlackWins;
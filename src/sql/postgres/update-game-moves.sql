@@ -0,0 +1,3 @@
+UPDATE Game
+SET Moves = $2
+WHERE GameId = $1;
@@ -0,0 +1,3 @@
+UPDATE IngestionRun
+SET GamesParsed = $2, GamesInserted = $3, GamesRejected = $4, GamesWithErrors = $5, FinishedAt = CURRENT_TIMESTAMP
+WHERE RunId = $1;
@@ -0,0 +1,4 @@
+DROP VIEW IF EXISTS MoveDescriptor;
+CREATE VIEW MoveDescriptor AS
+SELECT GameId, Num, Descriptor >> 31 as IsWhite, (Descriptor >> 28) & 7 as MovedPiece, (Descriptor >> 25) & 7 as FromRow, ((Descriptor >> 22) & 7) as FromColumn, (Descriptor >> 19) & 7 as CapturedPiece, (Descriptor >> 16) & 7 as ToRow, ((Descriptor >> 13) & 7) as ToColumn, (Descriptor >> 10) & 7 as PromotedPiece, (Descriptor >> 9) & 1 as IsCheck, (Descriptor >> 8) & 1 as IsMate, (Descriptor & 255) as NAG
+FROM Move;
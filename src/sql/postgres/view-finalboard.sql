@@ -0,0 +1,4 @@
+DROP VIEW IF EXISTS FinalBoard;
+CREATE VIEW FinalBoard AS
+SELECT FCId, ARow & 15 AS A1, (ARow >> 4) & 15 AS A2, (ARow >> 8) & 15 AS A3, (ARow >> 12) & 15 AS A4, (ARow >> 16) & 15 AS A5, (ARow >> 20) & 15 AS A6, (ARow >> 24) & 15 AS A7, (ARow >> 28) & 15 AS A8, BRow & 15 AS B1, (BRow >> 4) & 15 AS B2, (BRow >> 8) & 15 AS B3, (BRow >> 12) & 15 AS B4, (BRow >> 16) & 15 AS B5, (BRow >> 20) & 15 AS B6, (BRow >> 24) & 15 AS B7, (BRow >> 28) & 15 AS B8, CRow & 15 AS C1, (CRow >> 4) & 15 AS C2, (CRow >> 8) & 15 AS C3, (CRow >> 12) & 15 AS C4, (CRow >> 16) & 15 AS C5, (CRow >> 20) & 15 AS C6, (CRow >> 24) & 15 AS C7, (CRow >> 28) & 15 AS C8, DRow & 15 AS D1, (DRow >> 4) & 15 AS D2, (DRow >> 8) & 15 AS D3, (DRow >> 12) & 15 AS D4, (DRow >> 16) & 15 AS D5, (DRow >> 20) & 15 AS D6, (DRow >> 24) & 15 AS D7, (DRow >> 28) & 15 AS D8, ERow & 15 AS E1, (ERow >> 4) & 15 AS E2, (ERow >> 8) & 15 AS E3, (ERow >> 12) & 15 AS E4, (ERow >> 16) & 15 AS E5, (ERow >> 20) & 15 AS E6, (ERow >> 24) & 15 AS E7, (ERow >> 28) & 15 AS E8, FRow & 15 AS F1, (FRow >> 4) & 15 AS F2, (FRow >> 8) & 15 AS F3, (FRow >> 12) & 15 AS F4, (FRow >> 16) & 15 AS F5, (FRow >> 20) & 15 AS F6, (FRow >> 24) & 15 AS F7, (FRow >> 28) & 15 AS F8, GRow & 15 AS G1, (GRow >> 4) & 15 AS G2, (GRow >> 8) & 15 AS G3, (GRow >> 12) & 15 AS G4, (GRow >> 16) & 15 AS G5, (GRow >> 20) & 15 AS G6, (GRow >> 24) & 15 AS G7, (GRow >> 28) & 15 AS G8, HRow & 15 AS H1, (HRow >> 4) & 15 AS H2, (HRow >> 8) & 15 AS H3, (HRow >> 12) & 15 AS H4, (HRow >> 16) & 15 AS H5, (HRow >> 20) & 15 AS H6, (HRow >> 24) & 15 AS H7, (HRow >> 28) & 15 AS H8
+FROM FinalConfiguration;
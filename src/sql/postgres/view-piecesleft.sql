@@ -0,0 +1,4 @@
+DROP VIEW IF EXISTS PiecesLeft;
+CREATE VIEW PiecesLeft AS
+SELECT FCId, (EndWhitePieces >> 12) & 15 as EndWhitePawns, (EndWhitePieces >> 9) & 7 as EndWhiteKnights, (EndWhitePieces >> 6) & 7 as EndWhiteBishops, (EndWhitePieces >> 3) & 7 as EndWhiteRooks, EndWhitePieces & 7 as EndWhiteQueens, (EndBlackPieces >> 12) & 15 as EndBlackPawns, (EndBlackPieces >> 9) & 7 as EndBlackKnights, (EndBlackPieces >> 6) & 7 as EndBlackBishops, (EndBlackPieces >> 3) & 7 as EndBlackRooks, EndBlackPieces & 7 as EndBlackQueens
+FROM FinalConfiguration;
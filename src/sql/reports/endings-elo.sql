@@ -0,0 +1,5 @@
+SELECT {elo_bucket} AS elo_bucket, {ending_columns}, COUNT(*) AS total_games
+FROM ({endgames}) e
+WHERE WhiteElo IS NOT NULL AND BlackElo IS NOT NULL
+GROUP BY {elo_bucket}
+ORDER BY MIN({elo});
@@ -0,0 +1,6 @@
+SELECT {opening_family} AS opening_family, {ending_columns}, COUNT(*) AS total_games
+FROM ({endgames}) e
+JOIN Opening o ON e.OpeningId = o.OpeningId
+WHERE {opening_family} IN ({openings})
+GROUP BY {opening_family}
+ORDER BY {opening_order};
@@ -0,0 +1,6 @@
+SELECT r.Name AS ruleset, {ending_columns}, COUNT(*) AS total_games
+FROM ({endgames}) e
+JOIN RuleSet r ON e.RuleSetId = r.RuleSetId
+WHERE WhiteElo IS NOT NULL AND BlackElo IS NOT NULL
+GROUP BY r.Name
+ORDER BY r.Name;
@@ -0,0 +1,9 @@
+SELECT EndgameType AS endgame_type, AdvantagedPlayer AS advantaged_player, {elo_bucket} AS elo_bucket, COUNT(*) AS total_games,
+SUM(CASE WHEN (AdvantagedPlayer = 'white' AND Result = 'White') OR (AdvantagedPlayer = 'black' AND Result = 'Black') THEN 1 ELSE 0 END) AS victories,
+SUM(CASE WHEN Result = 'Tie' THEN 1 ELSE 0 END) AS draws,
+ROUND(100.0 * SUM(CASE WHEN (AdvantagedPlayer = 'white' AND Result = 'White') OR (AdvantagedPlayer = 'black' AND Result = 'Black') THEN 1 ELSE 0 END) / COUNT(*), 2) AS win_pct,
+ROUND(100.0 * SUM(CASE WHEN Result = 'Tie' THEN 1 ELSE 0 END) / COUNT(*), 2) AS draw_pct
+FROM ({endgames}) e
+WHERE AdvantagedPlayer IS NOT NULL AND WhiteElo IS NOT NULL AND BlackElo IS NOT NULL AND EndgameType <> 'empty_endings'
+GROUP BY EndgameType, AdvantagedPlayer, {elo_bucket}
+ORDER BY EndgameType, AdvantagedPlayer, MIN({elo});
@@ -0,0 +1,7 @@
+SELECT {difference_bucket} AS elo_difference, COUNT(*) AS total_games,
+SUM(CASE WHEN (g.WhiteElo > g.BlackElo AND g.Result = 'White') OR (g.BlackElo > g.WhiteElo AND g.Result = 'Black') THEN 1 ELSE 0 END) AS higher_elo_wins,
+ROUND(100.0 * SUM(CASE WHEN (g.WhiteElo > g.BlackElo AND g.Result = 'White') OR (g.BlackElo > g.WhiteElo AND g.Result = 'Black') THEN 1 ELSE 0 END) / COUNT(*), 2) AS higher_elo_win_pct
+FROM Game g
+WHERE {filter} AND g.Result IN ('White', 'Black') AND g.WhiteElo IS NOT NULL AND g.BlackElo IS NOT NULL
+GROUP BY {difference_bucket}
+ORDER BY MIN({difference});
@@ -0,0 +1,6 @@
+SELECT {opening_family} AS opening_family, {elo_columns}, COUNT(*) AS total_games
+FROM Game g
+JOIN Opening o ON g.OpeningId = o.OpeningId
+WHERE {filter} AND g.WhiteElo IS NOT NULL AND g.BlackElo IS NOT NULL AND {opening_family} IN ({openings})
+GROUP BY {opening_family}
+ORDER BY {opening_order};
@@ -0,0 +1,6 @@
+SELECT {month} AS month, {opening_family} AS opening_family, COUNT(*) AS total_games
+FROM Game g
+JOIN Opening o ON g.OpeningId = o.OpeningId
+WHERE {filter} AND {opening_family} IN ({openings})
+GROUP BY {month}, {opening_family}
+ORDER BY {month}, {opening_order};
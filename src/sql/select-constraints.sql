@@ -0,0 +1,2 @@
+SELECT TABLE_NAME, CONSTRAINT_NAME FROM information_schema.TABLE_CONSTRAINTS
+WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME IN (:game, :move) AND CONSTRAINT_TYPE = 'FOREIGN KEY';
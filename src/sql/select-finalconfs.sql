@@ -0,0 +1 @@
+SELECT FCId, EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow FROM FinalConfiguration;
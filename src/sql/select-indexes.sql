@@ -0,0 +1,2 @@
+SELECT DISTINCT TABLE_NAME, INDEX_NAME FROM information_schema.STATISTICS
+WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME IN (:game, :move) AND INDEX_NAME <> 'PRIMARY';
@@ -0,0 +1 @@
+SELECT OpeningId, Name FROM Opening;
@@ -0,0 +1 @@
+SELECT PlayerId, Name FROM Player;
@@ -0,0 +1 @@
+SELECT RuleSetId, Name, URLId FROM RuleSet;
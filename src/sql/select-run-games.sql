@@ -0,0 +1 @@
+SELECT GameId FROM Game WHERE RunId <=> ? AND GameId >= ? ORDER BY GameId LIMIT ?;
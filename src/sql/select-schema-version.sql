@@ -0,0 +1 @@
+SELECT COALESCE(MAX(Version), 0) FROM SchemaVersion;
@@ -0,0 +1 @@
+SELECT RunId FROM IngestionRun WHERE SourceFile = :source_file AND PurgedAt IS NULL ORDER BY RunId;
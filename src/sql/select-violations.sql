@@ -0,0 +1,15 @@
+SELECT 'Move primary key' AS ConstraintName, COUNT(*) AS Violations FROM (SELECT m.GameId FROM Move m GROUP BY m.GameId, m.Num HAVING COUNT(*) > 1) d
+UNION ALL
+SELECT 'Move.GameId foreign key', COUNT(*) FROM Move m LEFT JOIN Game g ON m.GameId = g.GameId WHERE g.GameId IS NULL
+UNION ALL
+SELECT 'Game.RuleSetId foreign key', COUNT(*) FROM Game g LEFT JOIN RuleSet r ON g.RuleSetId = r.RuleSetId WHERE r.RuleSetId IS NULL
+UNION ALL
+SELECT 'Game.OpeningId foreign key', COUNT(*) FROM Game g LEFT JOIN Opening o ON g.OpeningId = o.OpeningId WHERE g.OpeningId IS NOT NULL AND o.OpeningId IS NULL
+UNION ALL
+SELECT 'Game.FCId foreign key', COUNT(*) FROM Game g LEFT JOIN FinalConfiguration f ON g.FCId = f.FCId WHERE f.FCId IS NULL
+UNION ALL
+SELECT 'Game.White foreign key', COUNT(*) FROM Game g LEFT JOIN Player p ON g.White = p.PlayerId WHERE g.White IS NOT NULL AND p.PlayerId IS NULL
+UNION ALL
+SELECT 'Game.Black foreign key', COUNT(*) FROM Game g LEFT JOIN Player p ON g.Black = p.PlayerId WHERE g.Black IS NOT NULL AND p.PlayerId IS NULL
+UNION ALL
+SELECT 'Game.RunId foreign key', COUNT(*) FROM Game g LEFT JOIN IngestionRun i ON g.RunId = i.RunId WHERE g.RunId IS NOT NULL AND i.RunId IS NULL;
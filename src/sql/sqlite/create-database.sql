@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = OFF;
+DROP VIEW IF EXISTS MoveDescriptor;
+DROP VIEW IF EXISTS FinalBoard;
+DROP VIEW IF EXISTS PiecesLeft;
+DROP TABLE IF EXISTS OpeningStats;
+DROP TABLE IF EXISTS EndgameStats;
+DROP TABLE IF EXISTS Move;
+DROP TABLE IF EXISTS Game;
+DROP TABLE IF EXISTS IngestionRun;
+DROP TABLE IF EXISTS FinalConfiguration;
+DROP TABLE IF EXISTS RuleSet;
+DROP TABLE IF EXISTS Player;
+DROP TABLE IF EXISTS Opening;
+DROP TABLE IF EXISTS SchemaVersion;
+PRAGMA foreign_keys = ON;
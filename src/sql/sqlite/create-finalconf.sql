@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS FinalConfiguration;
+CREATE TABLE IF NOT EXISTS FinalConfiguration (
+FCId INTEGER PRIMARY KEY NOT NULL,
+EndBlackPieces INTEGER NOT NULL CHECK (EndBlackPieces BETWEEN 0 AND 65535),
+EndWhitePieces INTEGER NOT NULL CHECK (EndWhitePieces BETWEEN 0 AND 65535),
+ARow INTEGER NOT NULL CHECK (ARow >= 0),
+BRow INTEGER NOT NULL CHECK (BRow >= 0),
+CRow INTEGER NOT NULL CHECK (CRow >= 0),
+DRow INTEGER NOT NULL CHECK (DRow >= 0),
+ERow INTEGER NOT NULL CHECK (ERow >= 0),
+FRow INTEGER NOT NULL CHECK (FRow >= 0),
+GRow INTEGER NOT NULL CHECK (GRow >= 0),
+HRow INTEGER NOT NULL CHECK (HRow >= 0),
+CONSTRAINT UC_FinalConfiguration UNIQUE (EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow)
+);
@@ -0,0 +1,25 @@
+DROP TABLE IF EXISTS Game;
+CREATE TABLE IF NOT EXISTS Game (
+GameId INTEGER PRIMARY KEY NOT NULL,
+RuleSetId INTEGER NOT NULL,
+OpeningId INTEGER NULL,
+FCId INTEGER NOT NULL,
+White INTEGER NULL,
+WhiteElo INTEGER NULL CHECK (WhiteElo BETWEEN 0 AND 65535),
+WhiteTitle TEXT NULL CHECK (WhiteTitle IN ('BOT', 'LM', 'GM', 'WGM', 'IM', 'WIM', 'FM', 'WFM', 'CM', 'WCM', 'NM', 'WNM', 'GR', 'MC', 'MN', 'M')),
+Black INTEGER NULL,
+BlackElo INTEGER NULL CHECK (BlackElo BETWEEN 0 AND 65535),
+BlackTitle TEXT NULL CHECK (BlackTitle IN ('BOT', 'LM', 'GM', 'WGM', 'IM', 'WIM', 'FM', 'WFM', 'CM', 'WCM', 'NM', 'WNM', 'GR', 'MC', 'MN', 'M')),
+StartTime INTEGER NULL CHECK (StartTime BETWEEN 0 AND 65535),
+Increment INTEGER NULL CHECK (Increment BETWEEN 0 AND 255),
+Result TEXT CHECK (Result IN ('Null', 'White', 'Black', 'Tie')),
+Termination TEXT CHECK (Termination IN ('Unterminated', 'Normal', 'TimeForfeit', 'RulesInfraction', 'Abandoned')),
+DateTime TEXT,
+HasClock BOOLEAN,
+HasEvaluations BOOLEAN,
+FOREIGN KEY (RuleSetId) REFERENCES RuleSet(RuleSetId),
+FOREIGN KEY (OpeningId) REFERENCES Opening(OpeningId),
+FOREIGN KEY (FCId) REFERENCES FinalConfiguration(FCId),
+FOREIGN KEY (White) REFERENCES Player(PlayerId),
+FOREIGN KEY (Black) REFERENCES Player(PlayerId)
+);
@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS Move;
+CREATE TABLE IF NOT EXISTS Move (
+GameId INTEGER NOT NULL,
+Num INTEGER NOT NULL CHECK (Num BETWEEN 0 AND 65535),
+Descriptor INTEGER NOT NULL CHECK (Descriptor BETWEEN 0 AND 4294967295),
+EvalFloat REAL NULL,
+EvalInt INTEGER NULL CHECK (EvalInt BETWEEN -128 AND 127),
+Clock TEXT NULL,
+PRIMARY KEY (GameId, Num),
+FOREIGN KEY (GameId) REFERENCES Game(GameId)
+) WITHOUT ROWID;
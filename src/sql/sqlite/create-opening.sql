@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS Opening;
+CREATE TABLE IF NOT EXISTS Opening (
+OpeningId INTEGER PRIMARY KEY NOT NULL,
+Name TEXT NOT NULL CHECK (length(Name) <= 100),
+EcoLetter TEXT NOT NULL CHECK (EcoLetter IN ('Q', 'A', 'B', 'C', 'D', 'E')),
+EcoNumber INTEGER NOT NULL CHECK (EcoNumber BETWEEN 0 AND 99),
+UNIQUE (Name)
+);
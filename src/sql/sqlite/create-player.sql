@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS Player;
+CREATE TABLE IF NOT EXISTS Player (
+PlayerId INTEGER PRIMARY KEY NOT NULL,
+Name TEXT NOT NULL CHECK (length(Name) <= 31),
+UNIQUE (Name)
+);
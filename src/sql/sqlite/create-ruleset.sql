@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS RuleSet;
+CREATE TABLE IF NOT EXISTS RuleSet (
+RuleSetId INTEGER PRIMARY KEY NOT NULL,
+Name TEXT NOT NULL CHECK (length(Name) <= 31),
+URLId TEXT NOT NULL CHECK (length(URLId) <= 8),
+Kind TEXT NOT NULL CHECK (Kind IN ('Arena', 'Swiss', 'Game')),
+CONSTRAINT UC_RuleSet UNIQUE (Name, URLId)
+);
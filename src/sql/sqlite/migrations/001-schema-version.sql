@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS SchemaVersion (
+Version INTEGER PRIMARY KEY NOT NULL CHECK (Version > 0),
+AppliedAt TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS IngestionRun (
+RunId INTEGER PRIMARY KEY NOT NULL,
+Version TEXT NOT NULL,
+Features TEXT NOT NULL,
+Arguments TEXT NOT NULL,
+SampleSeed TEXT NULL,
+SampleSize INTEGER NULL CHECK (SampleSize >= 0),
+GamesParsed INTEGER NOT NULL DEFAULT 0 CHECK (GamesParsed >= 0),
+GamesInserted INTEGER NOT NULL DEFAULT 0 CHECK (GamesInserted >= 0),
+GamesRejected INTEGER NOT NULL DEFAULT 0 CHECK (GamesRejected >= 0),
+GamesWithErrors INTEGER NOT NULL DEFAULT 0 CHECK (GamesWithErrors >= 0),
+StartedAt TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+FinishedAt TEXT NULL
+);
+ALTER TABLE Game ADD COLUMN RunId INTEGER NULL REFERENCES IngestionRun(RunId);
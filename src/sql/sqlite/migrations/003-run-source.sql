@@ -0,0 +1,2 @@
+ALTER TABLE IngestionRun ADD COLUMN SourceFile TEXT NULL;
+ALTER TABLE IngestionRun ADD COLUMN PurgedAt TEXT NULL;
@@ -0,0 +1 @@
+ALTER TABLE Game ADD COLUMN Moves BLOB NULL;
@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS OpeningStats (
+Month TEXT NOT NULL,
+OpeningFamily TEXT NOT NULL,
+Speed TEXT NOT NULL CHECK (Speed IN ('UltraBullet', 'Bullet', 'Blitz', 'Rapid', 'Classical', 'Correspondence')),
+EloBucket INTEGER NOT NULL CHECK (EloBucket >= 0),
+Games INTEGER NOT NULL,
+WhiteWins INTEGER NOT NULL,
+Draws INTEGER NOT NULL,
+BlackWins INTEGER NOT NULL,
+PRIMARY KEY (Month, OpeningFamily, Speed, EloBucket)
+);
+CREATE TABLE IF NOT EXISTS EndgameStats (
+Signature TEXT NOT NULL,
+EloBucket INTEGER NOT NULL CHECK (EloBucket >= 0),
+Result TEXT NOT NULL CHECK (Result IN ('Null', 'White', 'Black', 'Tie')),
+Games INTEGER NOT NULL,
+PRIMARY KEY (Signature, EloBucket, Result)
+);
+INSERT INTO OpeningStats (Month, OpeningFamily, Speed, EloBucket, Games, WhiteWins, Draws, BlackWins)
+SELECT COALESCE(STRFTIME('%Y-%m', g.DateTime), ''), COALESCE(SUBSTR(o.Name, 1, INSTR(o.Name || ':', ':') - 1), ''), CASE WHEN g.StartTime IS NULL THEN 'Correspondence' WHEN g.StartTime + 40 * g.Increment < 30 THEN 'UltraBullet' WHEN g.StartTime + 40 * g.Increment < 180 THEN 'Bullet' WHEN g.StartTime + 40 * g.Increment < 480 THEN 'Blitz' WHEN g.StartTime + 40 * g.Increment < 1500 THEN 'Rapid' ELSE 'Classical' END, COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), COUNT(*), SUM(CASE WHEN g.Result = 'White' THEN 1 ELSE 0 END), SUM(CASE WHEN g.Result = 'Tie' THEN 1 ELSE 0 END), SUM(CASE WHEN g.Result = 'Black' THEN 1 ELSE 0 END)
+FROM Game g LEFT JOIN Opening o ON g.OpeningId = o.OpeningId
+WHERE true
+GROUP BY 1, 2, 3, 4;
+INSERT INTO EndgameStats (Signature, EloBucket, Result, Games)
+SELECT 'K' || SUBSTR('QQQQQQQ', 1, p.EndWhiteQueens) || SUBSTR('RRRRRRR', 1, p.EndWhiteRooks) || SUBSTR('BBBBBBB', 1, p.EndWhiteBishops) || SUBSTR('NNNNNNN', 1, p.EndWhiteKnights) || SUBSTR('PPPPPPPP', 1, p.EndWhitePawns) || 'vK' || SUBSTR('QQQQQQQ', 1, p.EndBlackQueens) || SUBSTR('RRRRRRR', 1, p.EndBlackRooks) || SUBSTR('BBBBBBB', 1, p.EndBlackBishops) || SUBSTR('NNNNNNN', 1, p.EndBlackKnights) || SUBSTR('PPPPPPPP', 1, p.EndBlackPawns), COALESCE((g.WhiteElo + g.BlackElo) / 200 * 100, 0), COALESCE(g.Result, 'Null'), COUNT(*)
+FROM Game g JOIN PiecesLeft p ON g.FCId = p.FCId
+WHERE true
+GROUP BY 1, 2, 3;
@@ -0,0 +1,3 @@
+UPDATE Game
+SET Moves = :moves
+WHERE GameId = :game_id;
@@ -0,0 +1,3 @@
+UPDATE IngestionRun
+SET GamesParsed = :games_parsed, GamesInserted = :games_inserted, GamesRejected = :games_rejected, GamesWithErrors = :games_with_errors, FinishedAt = CURRENT_TIMESTAMP
+WHERE RunId = :run_id;
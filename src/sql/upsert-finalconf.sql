@@ -0,0 +1,3 @@
+INSERT INTO FinalConfiguration (EndBlackPieces, EndWhitePieces, ARow, BRow, CRow, DRow, ERow, FRow, GRow, HRow)
+VALUES (:black_pieces, :white_pieces, :a_row, :b_row, :c_row, :d_row, :e_row, :f_row, :g_row, :h_row)
+ON DUPLICATE KEY UPDATE FCId = LAST_INSERT_ID(FCId);
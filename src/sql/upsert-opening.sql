@@ -0,0 +1,3 @@
+INSERT INTO Opening (Name, EcoLetter, EcoNumber)
+VALUES (:name, :eco_letter, :eco_number)
+ON DUPLICATE KEY UPDATE OpeningId = LAST_INSERT_ID(OpeningId);
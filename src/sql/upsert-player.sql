@@ -0,0 +1,3 @@
+INSERT INTO Player (Name)
+VALUES (:name)
+ON DUPLICATE KEY UPDATE PlayerId = LAST_INSERT_ID(PlayerId);
@@ -0,0 +1,3 @@
+INSERT INTO RuleSet (Name, URLId, Kind)
+VALUES (:name, :url_id, :kind)
+ON DUPLICATE KEY UPDATE RuleSetId = LAST_INSERT_ID(RuleSetId);
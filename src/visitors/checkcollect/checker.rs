@@ -7,7 +7,9 @@ use pgn_reader::{RawComment, RawHeader, Visitor};
 use shakmaty::Outcome;
 
 #[cfg(feature = "full-check")]
-use lichess::attributes::BoardConfiguration;
+use lichess::attributes::{BoardConfiguration, MoveDescriptor};
+#[cfg(feature = "full-check")]
+use lichess::report::{ErrorCategory, ToErrorRecord};
 #[cfg(feature = "full-check")]
 use pgn_reader::SanPlus;
 #[cfg(feature = "full-check")]
@@ -16,18 +18,20 @@ use shakmaty::{Chess, Position};
 use crate::visitors::comment_iterator::CommentIterator;
 use lichess::{
     attributes::{
-        Clk, Date, Eco, Elo, Eval, MoveDescriptor, Opening, Player, Result as ResultAttr, RuleSet,
-        Termination, TimeControl, Title, UTCDate, UTCTime, attribute::StringAttribute,
+        Clk, Date, Eco, Elo, Eval, Opening, Player, Result as ResultAttr, RuleSet, Termination,
+        TimeControl, Title, UTCDate, UTCTime, attribute::StringAttribute,
     },
     constants::{
         comments::{CLK, EVAL},
         headers::{
             BLACK, BLACK_ELO, BLACK_RATING_DIFF, BLACK_TITLE, DATE, ECO, EVENT, LICHESS_ID,
-            OPENING, RESULT, ROUND, SITE, TERMINATION, TIME_CONTROL, UTC_DATE, UTC_TIME, WHITE,
-            WHITE_ELO, WHITE_RATING_DIFF, WHITE_TITLE,
+            OPENING, REJECT_REASON, RESULT, ROUND, SITE, TERMINATION, TIME_CONTROL, UTC_DATE,
+            UTC_TIME, WHITE, WHITE_ELO, WHITE_RATING_DIFF, WHITE_TITLE,
         },
     },
-    loneerror, nullerror, valuederror,
+    keyederror, loneerror, nullerror,
+    report::ErrorReport,
+    valuederror,
 };
 
 /// Checker visitor for a PGN reader.
@@ -40,6 +44,10 @@ pub struct Checker {
     moves: usize,
     /// Whether or not the checking has yield any errors.
     pub has_errors: bool,
+    /// Number of games in which the checking found errors.
+    pub games_with_errors: usize,
+    /// Report of the errors found while checking.
+    pub report: ErrorReport,
 
     /// The board for checking move validity.
     #[cfg(feature = "full-check")]
@@ -156,22 +164,16 @@ impl Checker {
         match key {
             CLK => {
                 if let Err(e) = Clk::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             EVAL => {
                 if let Err(e) = Eval::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             key => {
-                error!(
-                    "{} - New comment found: {} <- {:?}",
-                    self.games,
-                    String::from_utf8_lossy(key),
-                    key
-                );
-                self.has_errors = true;
+                keyederror!(self, UnknownComment, "New comment found", key, value, key);
             }
         }
     }
@@ -266,31 +268,28 @@ impl Checker {
         match key {
             SITE => {
                 self.site = true;
+                self.report.set_lichess_id(value);
                 if from_utf8(value).is_err() {
-                    error!(
-                        "{} - Site is not UTF-8: {} <- {:?}",
-                        self.games,
-                        String::from_utf8_lossy(key),
-                        key
-                    );
-                    self.has_errors = true;
+                    keyederror!(self, InvalidUtf8, "Site is not UTF-8", key, value, value);
                 }
             }
             LICHESS_ID => {
+                self.report.set_lichess_id(value);
                 if from_utf8(value).is_err() {
-                    error!(
-                        "{} - LichessId is not UTF-8: {} <- {:?}",
-                        self.games,
-                        String::from_utf8_lossy(key),
-                        key
+                    keyederror!(
+                        self,
+                        InvalidUtf8,
+                        "LichessId is not UTF-8",
+                        key,
+                        value,
+                        value
                     );
-                    self.has_errors = true;
                 }
             }
             TIME_CONTROL => {
                 self.time_control = true;
                 if let Err(e) = TimeControl::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             RESULT => {
@@ -298,7 +297,7 @@ impl Checker {
                 match ResultAttr::try_from(value) {
                     Ok(result) => self.current_result = result,
                     Err(e) => {
-                        valuederror!(self, e);
+                        valuederror!(self, e, key, value);
                     }
                 }
             }
@@ -307,7 +306,7 @@ impl Checker {
                 match Termination::try_from(value) {
                     Ok(termination) => self.current_termination = termination,
                     Err(e) => {
-                        valuederror!(self, e);
+                        valuederror!(self, e, key, value);
                     }
                 }
             }
@@ -316,11 +315,13 @@ impl Checker {
                 match (Date::try_from(value), self.current_date) {
                     (Ok(date), Some(utc_date)) => {
                         if date.0 != utc_date {
-                            error!(
-                                "{} - Date ({date}) is different than UTCDate ({utc_date})",
-                                self.games
+                            keyederror!(
+                                self,
+                                Inconsistency,
+                                format!("Date ({date}) is different than UTCDate ({utc_date})"),
+                                key,
+                                value
                             );
-                            self.has_errors = true;
                         }
                     }
                     (Ok(date), None) => {
@@ -331,7 +332,7 @@ impl Checker {
                         }
                     }
                     (Err(e), _) => {
-                        valuederror!(self, e);
+                        valuederror!(self, e, key, value);
                     }
                 }
             }
@@ -340,11 +341,13 @@ impl Checker {
                 match (UTCDate::try_from(value), self.current_date) {
                     (Ok(utc_date), Some(date)) => {
                         if utc_date.0 != date {
-                            error!(
-                                "{} - UTC Date ({utc_date}) is different than Date ({date})",
-                                self.games
+                            keyederror!(
+                                self,
+                                Inconsistency,
+                                format!("UTC Date ({utc_date}) is different than Date ({date})"),
+                                key,
+                                value
                             );
-                            self.has_errors = true;
                         }
                     }
                     (Ok(utc_date), None) => {
@@ -355,32 +358,32 @@ impl Checker {
                         }
                     }
                     (Err(e), _) => {
-                        valuederror!(self, e);
+                        valuederror!(self, e, key, value);
                     }
                 }
             }
             UTC_TIME => {
                 self.utc_time = true;
                 if let Err(e) = UTCTime::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             OPENING => {
                 self.opening = true;
                 if let Err(e) = self.current_opening.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             ECO => {
                 self.eco = true;
                 if let Err(e) = Eco::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             EVENT => {
                 self.event = true;
                 if let Err(e) = self.current_ruleset.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             ROUND => {
@@ -389,37 +392,42 @@ impl Checker {
                     loneerror!("There is a round different from \"-\"", self);
                 }
             }
+            REJECT_REASON => (),
             BLACK => {
                 self.black = true;
                 if let Err(e) = self.current_player.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             BLACK_ELO => {
                 self.black_elo = true;
                 if let Err(e) = Elo::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             BLACK_RATING_DIFF => {
                 //self.black_rating_diff = true;
                 match from_utf8(value) {
                     Err(_) => {
-                        error!(
-                            "{} - Black rating diff is not UTF-8: {} <- {:?}",
-                            self.games,
-                            String::from_utf8_lossy(value),
+                        keyederror!(
+                            self,
+                            InvalidUtf8,
+                            "Black rating diff is not UTF-8",
+                            key,
+                            value,
                             value
                         );
-                        self.has_errors = true;
                     }
                     Ok(value) => {
                         if value.parse::<i16>().is_err() {
-                            error!(
-                                "{} - Black rating diff is not a valid signed integer: {}",
-                                self.games, value,
+                            keyederror!(
+                                self,
+                                InvalidValue,
+                                "Black rating diff is not a valid signed integer",
+                                key,
+                                value.as_bytes(),
+                                value.as_bytes()
                             );
-                            self.has_errors = true;
                         }
                     }
                 }
@@ -427,40 +435,44 @@ impl Checker {
             BLACK_TITLE => {
                 //self.black_title = true;
                 if let Err(e) = Title::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             WHITE => {
                 self.white = true;
                 if let Err(e) = self.current_player.fill_ascii(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             WHITE_ELO => {
                 self.white_elo = true;
                 if let Err(e) = Elo::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             WHITE_RATING_DIFF => {
                 //self.white_rating_diff = true;
                 match from_utf8(value) {
                     Err(_) => {
-                        error!(
-                            "{} - White rating diff is not UTF-8: {} <- {:?}",
-                            self.games,
-                            String::from_utf8_lossy(value),
+                        keyederror!(
+                            self,
+                            InvalidUtf8,
+                            "White rating diff is not UTF-8",
+                            key,
+                            value,
                             value
                         );
-                        self.has_errors = true;
                     }
                     Ok(value) => {
                         if value.parse::<i16>().is_err() {
-                            error!(
-                                "{} - White rating diff is not a valid signed integer: {}",
-                                self.games, value,
+                            keyederror!(
+                                self,
+                                InvalidValue,
+                                "White rating diff is not a valid signed integer",
+                                key,
+                                value.as_bytes(),
+                                value.as_bytes()
                             );
-                            self.has_errors = true;
                         }
                     }
                 }
@@ -468,17 +480,11 @@ impl Checker {
             WHITE_TITLE => {
                 //self.white_title = true;
                 if let Err(e) = Title::try_from(value) {
-                    valuederror!(self, e);
+                    valuederror!(self, e, key, value);
                 }
             }
             key => {
-                error!(
-                    "{} - New header found: {} <- {:?}",
-                    self.games,
-                    String::from_utf8_lossy(key),
-                    key
-                );
-                self.has_errors = true;
+                keyederror!(self, UnknownHeader, "New header found", key, value, key);
             }
         }
     }
@@ -494,11 +500,16 @@ impl Visitor for Checker {
     #[cfg(feature = "full-check")]
     fn san(&mut self, _san: SanPlus) {
         self.moves += 1;
-        if let Err(_) = MoveDescriptor::from_and_play_san(&_san, &mut self.chess) {
+        if let Err(e) = MoveDescriptor::from_and_play_san(&_san, &mut self.chess) {
             error!(
+                game = self.games,
+                kind = ErrorCategory::InvalidSan.as_str();
                 "{}.{} - Invalid SAN move played: {_san}",
                 self.games, self.moves
             );
+            let mut record = e.to_record(self.games);
+            record.category = ErrorCategory::InvalidSan;
+            self.report.push(record);
             self.has_errors = true;
         }
     }
@@ -517,6 +528,12 @@ impl Visitor for Checker {
 
     fn end_game(&mut self) {
         self.check_game();
+        if !self.report.game_errors().is_empty() {
+            self.games_with_errors += 1;
+        }
+        if let Err(e) = self.report.end_game() {
+            error!(game = self.games; "{} - Error report writing error: {}", self.games, e);
+        }
         self.new_game();
         if self.games % 1000000 == 0 {
             info!("Checked {} games.", self.games);
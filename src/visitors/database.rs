@@ -1,38 +1,223 @@
 //! A visitor that inserts the elements of a PGN file to the database.
 
-use log::{error, info};
+use std::fmt::Display;
+
+use log::{error, info, warn};
 use pgn_reader::{Nag, RawComment, RawHeader, SanPlus, Visitor};
 use shakmaty::Outcome;
 
 use crate::{
-    adapter::{Connection, DatabaseAdapter},
+    adapter::{Connection, DatabaseAdapter, IngestionRun, MoveStorage, Names},
+    interrupt::interrupted,
     visitors::comment_iterator::CommentIterator,
 };
-use lichess::data::Data;
+use lichess::{
+    data::{Data, Game, Move},
+    report::{ErrorCategory, ErrorRecord},
+};
 
-/// A visitor that inserts the elements of a PGN file to the database.
+/// A visitor that inserts the elements of a PGN file to the database, through any of the adapters.
 #[derive(Debug)]
-pub struct Database {
+pub struct Database<A: DatabaseAdapter = Connection> {
     /// Connection to the database.
-    database_connection: Connection,
+    database_connection: A,
     /// Current data as it is being collected.
     pub data: Data,
     /// Whether there were or not errors in the insertion to the database.
     pub has_errors: bool,
+    /// Whether the games with errors must be skipped instead of inserted.
+    pub skip_invalid: bool,
+    /// Number of games accumulated before inserting them together in one transaction. If it is 1, each game is inserted as soon as it is read.
+    pub batch_size: usize,
+    /// Games accumulated and their moves, waiting to be inserted.
+    batch: Vec<(Game, Vec<Move>)>,
+    /// Numbers of the games accumulated, to report their errors.
+    batch_games: Vec<usize>,
+    /// Number of games inserted into the database.
+    pub inserted: usize,
+    /// Number of games rejected because of their errors, and thus not inserted.
+    pub rejected: usize,
+    /// Number of games with errors in their data, either found by the checker before their insertion or while parsing them.
+    pub with_errors: usize,
+    /// Record of the current run, if it is kept in the database.
+    run: Option<IngestionRun>,
 }
 
 impl Database {
-    /// Creates a new database serializer from the password.
-    pub fn new(db_url: &str, rebuild: bool) -> Result<Self, <Connection as DatabaseAdapter>::Error> {
-        Ok(Self {
-            database_connection: Connection::initialize_database(db_url, rebuild)?,
+    /// Creates a new database serializer from the password and the names of the database and its tables.
+    pub fn new(
+        db_url: &str,
+        names: Names,
+        rebuild: bool,
+    ) -> Result<Self, <Connection as DatabaseAdapter>::Error> {
+        Ok(Self::from_connection(Connection::initialize_database(
+            db_url, names, rebuild,
+        )?))
+    }
+
+    /// Checks that the schema of the database has the version this program inserts into.
+    ///
+    /// # Errors
+    /// Will return a [`Box`]ed [`Error`](std::error::Error) if the query fails or the schema has another version.
+    pub fn check_schema_version(&mut self) -> Result<&mut Self, Box<dyn std::error::Error>> {
+        self.database_connection.check_schema_version()?;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of IDs each cache of the connection keeps, loading the IDs already in the database into them if `prewarm` is set.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the loading fails.
+    pub fn set_cache(
+        &mut self,
+        capacity: usize,
+        prewarm: bool,
+    ) -> Result<&mut Self, <Connection as DatabaseAdapter>::Error> {
+        self.database_connection.set_cache_size(capacity);
+        if prewarm {
+            self.database_connection.prewarm_cache()?;
+        }
+        Ok(self)
+    }
+
+    /// Sets how the connection stores the moves of the games.
+    pub const fn set_move_storage(&mut self, move_storage: MoveStorage) -> &mut Self {
+        self.database_connection.set_move_storage(move_storage);
+        self
+    }
+
+    /// Logs the hit rates of the caches of the connection.
+    pub fn log_cache_hit_rates(&self) {
+        self.database_connection.log_cache_hit_rates();
+    }
+}
+
+impl<A: DatabaseAdapter> Database<A> {
+    /// Creates a new database serializer that inserts through an already initialized connection.
+    pub fn from_connection(database_connection: A) -> Self {
+        Self {
+            database_connection,
             data: Data::default(),
             has_errors: false,
-        })
+            skip_invalid: false,
+            batch_size: 1,
+            batch: Vec::new(),
+            batch_games: Vec::new(),
+            inserted: 0,
+            rejected: 0,
+            with_errors: 0,
+            run: None,
+        }
+    }
+
+    /// Gets the connection to the database.
+    pub const fn connection(&self) -> &A {
+        &self.database_connection
+    }
+
+    /// Drops the foreign keys and indexes of the Game and Move tables, so that they aren't checked on every insertion.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if any of the drops fail.
+    pub fn defer_constraints(&mut self) -> Result<&mut Self, A::Error> {
+        self.database_connection.defer_constraints()?;
+        Ok(self)
+    }
+
+    /// Validates the deferred constraints and adds them back if none of them is violated, returning the violated ones along with their number of violations otherwise.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the validation or the addition fail.
+    pub fn restore_constraints(&mut self) -> Result<Vec<(String, u64)>, A::Error> {
+        self.database_connection.restore_constraints()
+    }
+
+    /// Records the start of the run in the database, so that the games inserted afterwards reference it.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the insertion fails.
+    pub fn start_run(&mut self, run: IngestionRun) -> Result<&mut Self, A::Error> {
+        let run_id = self.database_connection.start_run(&run)?;
+        info!("Recording the games as run {run_id}.");
+        self.run = Some(run);
+        Ok(self)
+    }
+
+    /// Records the counts of games of the run in the database, once it has finished or been interrupted, logging them as its summary. The games parsed of an interrupted run are its checkpoint, since every one of them was either inserted, rejected or reported.
+    ///
+    /// # Errors
+    /// Will return [`DatabaseAdapter::Error`] if the update fails.
+    pub fn finish_run(&mut self) -> Result<&mut Self, A::Error> {
+        info!(
+            "Run summary: {} games parsed, {} inserted, {} rejected and {} with errors.",
+            self.data.games, self.inserted, self.rejected, self.with_errors
+        );
+        if let Some(mut run) = self.run.take() {
+            run.games_parsed = self.data.games as u64;
+            run.games_inserted = self.inserted as u64;
+            run.games_rejected = self.rejected as u64;
+            run.games_with_errors = self.with_errors as u64;
+            self.database_connection.finish_run(&run)?;
+            if interrupted() {
+                info!(
+                    "Checkpoint recorded at game {} of the run.",
+                    run.games_parsed
+                );
+            }
+        }
+        Ok(self)
+    }
+
+    /// Records an insertion error of a game.
+    fn insertion_error<E: Display>(&mut self, game: usize, e: E) {
+        error!(game, kind = ErrorCategory::Insertion.as_str(); "{game} - Insertion error: {e}");
+        self.data
+            .report
+            .push(ErrorRecord::new(game, ErrorCategory::Insertion, e));
+        self.has_errors = true;
+    }
+
+    /// Inserts the games accumulated in the batch. If the batch fails, its games are inserted one by one so that only the ones at fault are reported, and their records are written apart from the ones of the game being read.
+    fn insert_batch(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+        if let Err(e) = self.database_connection.insert_batch(&self.batch) {
+            warn!(
+                "Batch insertion error, inserting its {} games one by one: {e}",
+                self.batch.len()
+            );
+            let batch = std::mem::take(&mut self.batch);
+            for (game, games) in batch.iter().zip(std::mem::take(&mut self.batch_games)) {
+                if let Err(e) = self
+                    .database_connection
+                    .insert_batch(std::slice::from_ref(game))
+                {
+                    self.insertion_error(games, e);
+                } else {
+                    self.inserted += 1;
+                }
+            }
+            if let Err(e) = self.data.report.end_game() {
+                error!(game = self.data.games; "{} - Error report writing error: {}", self.data.games, e);
+            }
+        } else {
+            self.inserted += self.batch.len();
+        }
+        self.batch.clear();
+        self.batch_games.clear();
+    }
+
+    /// Inserts the games left in the batch, once the whole file has been read.
+    pub fn finish(&mut self) {
+        if !self.batch.is_empty() {
+            info!("Inserting the last batch of {} games.", self.batch.len());
+            self.insert_batch();
+        }
     }
 }
 
-impl Visitor for Database {
+impl<A: DatabaseAdapter> Visitor for Database<A> {
     type Result = ();
 
     fn header(&mut self, _key: &[u8], _value: RawHeader<'_>) {
@@ -59,9 +244,30 @@ impl Visitor for Database {
 
     fn end_game(&mut self) {
         self.data.end_game();
-        if let Err(e) = self.database_connection.insert_all(&self.data) {
-            error!("{} - Insertion error: {}", self.data.games, e);
-            self.has_errors = true;
+        let invalid = !self.data.report.game_errors().is_empty();
+        if invalid {
+            self.with_errors += 1;
+        }
+        if self.skip_invalid & invalid {
+            info!(
+                "{} - Skipping the insertion of an invalid game.",
+                self.data.games
+            );
+            self.rejected += 1;
+        } else if self.batch_size > 1 {
+            self.batch
+                .push((self.data.game.clone(), self.data.moves.clone()));
+            self.batch_games.push(self.data.games);
+        } else if let Err(e) = self.database_connection.insert_all(&self.data) {
+            self.insertion_error(self.data.games, e);
+        } else {
+            self.inserted += 1;
+        }
+        if let Err(e) = self.data.report.end_game() {
+            error!(game = self.data.games; "{} - Error report writing error: {}", self.data.games, e);
+        }
+        if self.batch.len() >= self.batch_size {
+            self.insert_batch();
         }
         self.data.new_game();
         if self.data.games % 1000 == 0 {
@@ -69,3 +275,107 @@ impl Visitor for Database {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{env, time::Instant};
+
+    use pgn_reader::BufferedReader;
+    use pretty_assertions::assert_eq;
+
+    use super::{Database, IngestionRun, Names};
+    use crate::{adapter::memory::MemoryConnection, reader::PGNReader};
+
+    /// Two games between the same players, with a different opening each.
+    const PGN: &str = r#"[Event "Rated Blitz game"]
+[Site "https://lichess.org/AbCdEfGh"]
+[White "Abbot"]
+[Black "Costello"]
+[Result "1-0"]
+[UTCDate "2017.04.01"]
+[UTCTime "11:32:01"]
+[WhiteElo "2100"]
+[BlackElo "2000"]
+[ECO "C20"]
+[Opening "King's Pawn Game"]
+[TimeControl "300+0"]
+[Termination "Normal"]
+
+1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0
+
+[Event "Rated Blitz game"]
+[Site "https://lichess.org/HgFeDcBa"]
+[White "Costello"]
+[Black "Abbot"]
+[Result "0-1"]
+[UTCDate "2017.04.01"]
+[UTCTime "11:40:12"]
+[WhiteElo "2005"]
+[BlackElo "2095"]
+[ECO "D06"]
+[Opening "Queen's Gambit"]
+[TimeControl "300+0"]
+[Termination "Normal"]
+
+1. d4 d5 2. c4 0-1
+"#;
+
+    /// Tests the insertion of the games through the in-memory database, one by one and in a batch.
+    #[test]
+    fn memory_database_test() {
+        for batch_size in [1, 2] {
+            let mut database = Database::from_connection(MemoryConnection::default());
+            database.batch_size = batch_size;
+            database.start_run(IngestionRun::default()).unwrap();
+            BufferedReader::new_cursor(PGN)
+                .read_all(&mut database)
+                .unwrap();
+            database.finish();
+            database.finish_run().unwrap();
+            assert!(!database.has_errors);
+            let connection = database.connection();
+            assert_eq!(connection.games.len(), 2);
+            assert_eq!(connection.players.len(), 2);
+            assert_eq!(connection.openings.len(), 2);
+            assert_eq!(connection.rulesets.len(), 1);
+            assert_eq!(connection.final_configurations.len(), 2);
+            assert_eq!(connection.moves.len(), 10);
+            assert_eq!(connection.games[0].white_id, connection.games[1].black_id);
+            assert_eq!(connection.games[0].run_id, Some(1));
+            assert_eq!(connection.runs[0].games_parsed, 2);
+            assert_eq!(connection.runs[0].games_inserted, 2);
+        }
+    }
+
+    /// Bench to test how much faster inserting the games in batches is than inserting them one by one, using the database given by the environment variable DATABASE_URL and the sample file given by BENCH_PGN. The database is rebuilt on every run.
+    #[test]
+    #[ignore = "needs a database and a sample file"]
+    fn batch_insertion_bench() {
+        let db_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pgn_file = env::var("BENCH_PGN").expect("BENCH_PGN must be set");
+
+        let mut elapsed = Vec::new();
+        for batch_size in [1, 100, 1000] {
+            let mut database = Database::new(&db_url, Names::default(), true).unwrap();
+            database.batch_size = batch_size;
+            let time = Instant::now();
+            PGNReader::new(&pgn_file)
+                .unwrap()
+                .read_all(&mut database)
+                .unwrap();
+            database.finish();
+            let batch_elapsed = time.elapsed();
+            assert!(!database.has_errors);
+            println!(
+                "Batch size {batch_size}: {} games in {} s, {:.0} games/s",
+                database.data.games,
+                batch_elapsed.as_secs_f32(),
+                database.data.games as f32 / batch_elapsed.as_secs_f32()
+            );
+            elapsed.push(batch_elapsed);
+        }
+
+        assert!(elapsed[1] < elapsed[0]);
+        assert!(elapsed[2] < elapsed[0]);
+    }
+}
@@ -0,0 +1,92 @@
+//! A visitor that exports the elements of a PGN file to CSV files with the layout of the database tables.
+
+use std::{io, path::Path};
+
+use log::{error, info};
+use pgn_reader::{Nag, RawComment, RawHeader, SanPlus, Visitor};
+use shakmaty::Outcome;
+
+use crate::{adapter::csv::CsvExport, visitors::comment_iterator::CommentIterator};
+use lichess::{
+    data::Data,
+    report::{ErrorCategory, ErrorRecord},
+};
+
+/// A visitor that exports the elements of a PGN file to CSV files with the layout of the database tables.
+#[derive(Debug)]
+pub struct Exporter {
+    /// Exporter to the CSV files.
+    export: CsvExport,
+    /// Current data as it is being collected.
+    pub data: Data,
+    /// Whether there were or not errors in the exportation to the CSV files.
+    pub has_errors: bool,
+}
+
+impl Exporter {
+    /// Creates a new CSV exporter to the directory.
+    ///
+    /// # Errors
+    /// Will return [`csv::Error`] if the directory or any of the files could not be created.
+    pub fn new<P: AsRef<Path>>(dir: P) -> csv::Result<Self> {
+        Ok(Self {
+            export: CsvExport::create(dir)?,
+            data: Data::default(),
+            has_errors: false,
+        })
+    }
+
+    /// Finishes the exportation, flushing every CSV file.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if any of the files could not be flushed.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.export.flush()
+    }
+}
+
+impl Visitor for Exporter {
+    type Result = ();
+
+    fn header(&mut self, _key: &[u8], _value: RawHeader<'_>) {
+        self.data.process_header(_key, _value.0);
+    }
+
+    fn san(&mut self, _san: SanPlus) {
+        self.data.new_move(_san);
+    }
+
+    fn nag(&mut self, _nag: Nag) {
+        self.data.add_nag(_nag);
+    }
+
+    fn comment(&mut self, _comment: RawComment<'_>) {
+        for (key, value) in CommentIterator::new(_comment.0) {
+            self.data.process_comment(key, value);
+        }
+    }
+
+    fn outcome(&mut self, _outcome: Option<Outcome>) {
+        self.data.check_outcome(_outcome);
+    }
+
+    fn end_game(&mut self) {
+        self.data.end_game();
+        if let Err(e) = self.export.write_all(&self.data) {
+            error!(game = self.data.games, kind = ErrorCategory::Insertion.as_str(); "{} - Exportation error: {}", self.data.games, e);
+            self.data.report.push(ErrorRecord::new(
+                self.data.games,
+                ErrorCategory::Insertion,
+                e,
+            ));
+            self.has_errors = true;
+        }
+        if let Err(e) = self.data.report.end_game() {
+            error!(game = self.data.games; "{} - Error report writing error: {}", self.data.games, e);
+        }
+        self.data.new_game();
+        if self.data.games % 1000 == 0 {
+            info!("Exported data of {} games.", self.data.games);
+        }
+    }
+}
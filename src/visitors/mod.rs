@@ -3,4 +3,8 @@
 pub mod checkcollect;
 pub mod comment_iterator;
 pub mod database;
+#[cfg(feature = "csv")]
+pub mod export;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod stats;
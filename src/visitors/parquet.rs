@@ -0,0 +1,94 @@
+//! A visitor that exports the games and moves of a PGN file to Parquet files partitioned by month.
+
+use std::path::Path;
+
+use log::{error, info};
+use pgn_reader::{Nag, RawComment, RawHeader, SanPlus, Visitor};
+use shakmaty::Outcome;
+
+use parquet::errors::Result;
+
+use crate::{adapter::parquet::ParquetExport, visitors::comment_iterator::CommentIterator};
+use lichess::{
+    data::Data,
+    report::{ErrorCategory, ErrorRecord},
+};
+
+/// A visitor that exports the games and moves of a PGN file to Parquet files partitioned by month.
+#[derive(Debug)]
+pub struct ParquetExporter {
+    /// Exporter to the Parquet files.
+    export: ParquetExport,
+    /// Current data as it is being collected.
+    pub data: Data,
+    /// Whether there were or not errors in the exportation to the Parquet files.
+    pub has_errors: bool,
+}
+
+impl ParquetExporter {
+    /// Creates a new Parquet exporter to the directory.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if the directory could not be created.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Ok(Self {
+            export: ParquetExport::create(dir)?,
+            data: Data::default(),
+            has_errors: false,
+        })
+    }
+
+    /// Finishes the exportation, writing the remaining rows and closing every Parquet file.
+    ///
+    /// # Errors
+    /// Will return [`parquet::errors::ParquetError`] if any of the files could not be written or closed.
+    pub fn finish(&mut self) -> Result<()> {
+        self.export.close()
+    }
+}
+
+impl Visitor for ParquetExporter {
+    type Result = ();
+
+    fn header(&mut self, _key: &[u8], _value: RawHeader<'_>) {
+        self.data.process_header(_key, _value.0);
+    }
+
+    fn san(&mut self, _san: SanPlus) {
+        self.data.new_move(_san);
+    }
+
+    fn nag(&mut self, _nag: Nag) {
+        self.data.add_nag(_nag);
+    }
+
+    fn comment(&mut self, _comment: RawComment<'_>) {
+        for (key, value) in CommentIterator::new(_comment.0) {
+            self.data.process_comment(key, value);
+        }
+    }
+
+    fn outcome(&mut self, _outcome: Option<Outcome>) {
+        self.data.check_outcome(_outcome);
+    }
+
+    fn end_game(&mut self) {
+        self.data.end_game();
+        if let Err(e) = self.export.write_all(&self.data) {
+            error!(game = self.data.games, kind = ErrorCategory::Insertion.as_str(); "{} - Exportation error: {}", self.data.games, e);
+            self.data.report.push(ErrorRecord::new(
+                self.data.games,
+                ErrorCategory::Insertion,
+                e,
+            ));
+            self.has_errors = true;
+        }
+        if let Err(e) = self.data.report.end_game() {
+            error!(game = self.data.games; "{} - Error report writing error: {}", self.data.games, e);
+        }
+        self.data.new_game();
+        if self.data.games % 1000 == 0 {
+            info!("Exported data of {} games.", self.data.games);
+        }
+    }
+}
@@ -25,7 +25,6 @@ pub struct Stats {
 
 impl Stats {
     /// Logs the PGN files stats.
-    #[allow(dead_code)]
     pub fn log(&self) {
         info!("File stats");
         info!("Games: {}", self.games);
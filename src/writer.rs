@@ -0,0 +1,173 @@
+//! Writer for the PGN files this scrapper generates from the games it reads.
+
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use log::{debug, trace};
+
+use lichess::{constants::headers::REJECT_REASON, report::ErrorRecord};
+
+#[cfg(feature = "zstd")]
+use zstd::Encoder;
+
+/// The buffered writer used for a PGN or ZSTD compressed PGN file.
+pub enum PGNWriter {
+    /// A buffered writer for a simple PGN file.
+    PGN(BufWriter<File>),
+    /// A buffered writer for a ZSTD compressed PGN file.
+    #[cfg(feature = "zstd")]
+    ZST(Encoder<'static, BufWriter<File>>),
+}
+
+impl PGNWriter {
+    /// Creates a new buffered writer to the path to the file provided.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the file could not be created.
+    pub fn create<P: AsRef<Path> + Debug>(path: P) -> io::Result<Self> {
+        trace!("PGNWriter create function.");
+        debug!("{path:?}");
+        if let Some(ext) = path.as_ref().extension() {
+            if ext == "zst" {
+                #[cfg(feature = "zstd")]
+                {
+                    Ok(Self::ZST(Encoder::new(
+                        BufWriter::new(File::create(path)?),
+                        0,
+                    )?))
+                }
+                #[cfg(not(feature = "zstd"))]
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "The feature zstd must be active to be able to write a zstd file",
+                ))
+            } else if ext == "pgn" {
+                Ok(Self::PGN(BufWriter::new(File::create(path)?)))
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Only zstd and pgn files are allowed",
+                ))
+            }
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Only zstd and pgn files are allowed",
+            ))
+        }
+    }
+
+    /// Gets the inner writer.
+    fn inner(&mut self) -> &mut dyn Write {
+        match self {
+            Self::PGN(file) => file,
+            #[cfg(feature = "zstd")]
+            Self::ZST(file) => file,
+        }
+    }
+
+    /// Writes the raw text of a game verbatim, making sure it ends with an empty line.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the game could not be written.
+    pub fn write_game(&mut self, game: &[u8]) -> io::Result<()> {
+        let writer = self.inner();
+        writer.write_all(game)?;
+        if !game.ends_with(b"\n\n") {
+            writer.write_all(if game.ends_with(b"\n") {
+                b"\n"
+            } else {
+                b"\n\n"
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Writes the raw text of a rejected game, preceded by a header that states the reasons it was rejected for.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the game could not be written.
+    pub fn write_reject(&mut self, game: &[u8], errors: &[ErrorRecord]) -> io::Result<()> {
+        let mut reason = String::new();
+        for error in errors {
+            if !reason.is_empty() {
+                reason.push_str("; ");
+            }
+            for char in error.to_string().chars() {
+                if (char == '"') | (char == '\\') {
+                    reason.push('\\');
+                }
+                reason.push(char);
+            }
+        }
+        let writer = self.inner();
+        writer.write_all(b"[")?;
+        writer.write_all(REJECT_REASON)?;
+        writeln!(writer, " \"{reason}\"]")?;
+        self.write_game(game)
+    }
+
+    /// Flushes the writer, finishing the compression if there is any.
+    ///
+    /// # Errors
+    /// Will return [`io::Error`] if the writer could not be flushed.
+    pub fn finish(self) -> io::Result<()> {
+        trace!("PGNWriter finish function.");
+        match self {
+            Self::PGN(mut file) => file.flush(),
+            #[cfg(feature = "zstd")]
+            Self::ZST(file) => file.finish()?.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{read, remove_file};
+
+    use lichess::{
+        constants::headers::REJECT_REASON,
+        report::{ErrorCategory, ErrorRecord},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::PGNWriter;
+
+    /// Game without the empty line after its moves, as it is at the end of some files.
+    const GAME: &[u8] = b"[Event \"Rated \\\"Blitz\\\" game\"]\n[Site \"https://lichess.org/abcdefgh\"]\n\n1. e4 { [%clk 0:03:00] } 1... e5 1-0\n";
+
+    /// Tests that the rejected games are written verbatim after the header with the reasons, escaped, and end with an empty line.
+    #[test]
+    fn write_reject_test() {
+        let path = std::env::temp_dir().join("lichess-write-reject-test.pgn");
+        let mut writer = PGNWriter::create(&path).unwrap();
+        let errors = [
+            ErrorRecord::new(1, ErrorCategory::NullHeader, "ECO is null"),
+            ErrorRecord::new(1, ErrorCategory::UnknownHeader, "New header found")
+                .with_key(b"Weird")
+                .with_value(b"a \"quoted\" \\ value"),
+        ];
+        writer.write_reject(GAME, &errors).unwrap();
+        writer.write_game(GAME).unwrap();
+        writer.finish().unwrap();
+
+        let mut expected = b"[".to_vec();
+        expected.extend_from_slice(REJECT_REASON);
+        expected.extend_from_slice(
+            b" \"null_header: ECO is null; unknown_header Weird = a \\\"quoted\\\" \\\\ value\"]\n",
+        );
+        expected.extend_from_slice(GAME);
+        expected.push(b'\n');
+        expected.extend_from_slice(GAME);
+        expected.push(b'\n');
+        assert_eq!(
+            String::from_utf8(read(&path).unwrap()).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+        remove_file(path).unwrap();
+    }
+}
@@ -8,35 +8,32 @@ use pretty_assertions::assert_eq;
 #[test]
 pub fn game_csv_bench() {
     use csv::{Reader, Writer};
-    use lichess::data::game::Game;
+    use lichess::{attributes::attribute::StringAttribute, data::game::Game};
     use std::{
         fs::{File, remove_file},
         io::{BufWriter, Write},
         time::Instant,
     };
     const EXECUTIONS: i32 = 1000000;
-    let game: Game = Game {
-        game_id: 9000,
-        site: "https://lichess.org/ABCDEFGH".into(),
-        time_control: "300+0".into(),
-        result: "0-1".into(),
-        termination: "Time forfeit".into(),
-        date: "2017.04.01".into(),
-        utc_date: "2017.04.01".into(),
-        utc_time: "11:32:01".into(),
-        opening: "Sicilian Defense: Old Sicilian".into(),
-        eco: "B30".into(),
-        event: "Rated Bullet tournament".into(),
-        round: "-".into(),
-        white: "Abbot".into(),
-        white_elo: "2100".into(),
-        white_title: "FM".into(),
-        white_rating_diff: "-4".into(),
-        black: "Costello".into(),
-        black_elo: "2000".into(),
-        black_title: String::new(),
-        black_rating_diff: "+1".into(),
+    let mut game = Game {
+        time_control: "300+0".parse().unwrap(),
+        result: "0-1".parse().unwrap(),
+        termination: "Time forfeit".parse().unwrap(),
+        utc_date: "2017.04.01".parse().unwrap(),
+        utc_time: "11:32:01".parse().unwrap(),
+        eco: "B30".parse().unwrap(),
+        black_elo: "2000".parse().unwrap(),
+        white_elo: "2100".parse().unwrap(),
+        white_title: Some("FM".parse().unwrap()),
+        has_clock: true,
+        ..Default::default()
     };
+    game.opening.fill("Sicilian Defense: Old Sicilian");
+    game.ruleset
+        .fill_str("Rated Bullet tournament https://lichess.org/tournament/yc1WW2Ox")
+        .unwrap();
+    game.black.fill("Costello");
+    game.white.fill("Abbot");
     #[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
     const SERIALIZED: &str = "games_ser.tmp";
     const CSVB: &str = "games_csvb.tmp";
@@ -53,29 +50,7 @@ pub fn game_csv_bench() {
         let mut file = Writer::from_path(SERIALIZED).unwrap();
         time = Instant::now();
         for _ in 0..EXECUTIONS {
-            file.serialize(Game {
-                game_id: 9000,
-                site: "https://lichess.org/ABCDEFGH".into(),
-                time_control: "300+0".into(),
-                result: "0-1".into(),
-                termination: "Time forfeit".into(),
-                date: "2017.04.01".into(),
-                utc_date: "2017.04.01".into(),
-                utc_time: "11:32:01".into(),
-                opening: "Sicilian Defense: Old Sicilian".into(),
-                eco: "B30".into(),
-                event: "Rated Bullet tournament".into(),
-                round: "-".into(),
-                white: "Abbot".into(),
-                white_elo: "2100".into(),
-                white_title: "FM".into(),
-                white_rating_diff: "-4".into(),
-                black: "Costello".into(),
-                black_elo: "2000".into(),
-                black_title: String::new(),
-                black_rating_diff: "+1".into(),
-            })
-            .unwrap();
+            file.serialize(&game).unwrap();
         }
 
         elapsed_serialized = time.elapsed();
@@ -86,26 +61,23 @@ pub fn game_csv_bench() {
 
         file.write_byte_record(
             &[
-                "GameId",
-                "Site",
-                "TimeControl",
-                "Result",
-                "Termination",
-                "Date",
-                "UTCDate",
-                "UTCTime",
-                "Opening",
-                "Eco",
-                "Event",
-                "Round",
-                "White",
-                "WhiteElo",
-                "WhiteRatingDiff",
-                "WhiteTitle",
-                "Black",
-                "BlackElo",
-                "BlackRatingDiff",
-                "BlackTitle",
+                "time_control",
+                "result",
+                "termination",
+                "utc_date",
+                "utc_time",
+                "opening",
+                "eco",
+                "ruleset",
+                "black",
+                "black_elo",
+                "black_title",
+                "white",
+                "white_elo",
+                "white_title",
+                "has_clock",
+                "has_evaluations",
+                "final_conf",
             ]
             .as_slice()
             .into(),
@@ -115,26 +87,29 @@ pub fn game_csv_bench() {
         for _ in 0..EXECUTIONS {
             file.write_byte_record(
                 &[
-                    &format!("{}", game.game_id),
-                    &game.site,
-                    &game.time_control,
-                    &game.result,
-                    &game.termination,
-                    &game.date,
-                    &game.utc_date,
-                    &game.utc_time,
-                    &game.opening,
-                    &game.eco,
-                    &game.event,
-                    &game.round,
-                    &game.white,
-                    &game.white_elo,
-                    &game.white_rating_diff,
-                    &game.white_title,
-                    &game.black,
-                    &game.black_elo,
-                    &game.black_rating_diff,
-                    &game.black_title,
+                    &game.time_control.to_string(),
+                    &game.result.to_string(),
+                    &game.termination.to_string(),
+                    &game.utc_date.0.to_pgn(),
+                    &game.utc_time.0.to_pgn(),
+                    &game.opening.0.clone(),
+                    &game.eco.to_string(),
+                    &game.ruleset.to_event(),
+                    &game.black.0.clone(),
+                    &game.black_elo.to_string(),
+                    &game
+                        .black_title
+                        .map(|title| title.to_string())
+                        .unwrap_or_default(),
+                    &game.white.0.clone(),
+                    &game.white_elo.to_string(),
+                    &game
+                        .white_title
+                        .map(|title| title.to_string())
+                        .unwrap_or_default(),
+                    &game.has_clock.to_string(),
+                    &game.has_evaluations.to_string(),
+                    &game.final_conf.to_hex(),
                 ]
                 .as_slice()
                 .into(),
@@ -148,52 +123,52 @@ pub fn game_csv_bench() {
         let mut file = Writer::from_path(CSVS).unwrap();
         time = Instant::now();
 
-        file.write_record(&[
-            "GameId",
-            "Site",
-            "TimeControl",
-            "Result",
-            "Termination",
-            "Date",
-            "UTCDate",
-            "UTCTime",
-            "Opening",
-            "Eco",
-            "Event",
-            "Round",
-            "White",
-            "WhiteElo",
-            "WhiteRatingDiff",
-            "WhiteTitle",
-            "Black",
-            "BlackElo",
-            "BlackRatingDiff",
-            "BlackTitle",
+        file.write_record([
+            "time_control",
+            "result",
+            "termination",
+            "utc_date",
+            "utc_time",
+            "opening",
+            "eco",
+            "ruleset",
+            "black",
+            "black_elo",
+            "black_title",
+            "white",
+            "white_elo",
+            "white_title",
+            "has_clock",
+            "has_evaluations",
+            "final_conf",
         ])
         .unwrap();
 
         for _ in 0..EXECUTIONS {
-            file.write_record(&[
-                &format!("{}", game.game_id),
-                &game.site,
-                &game.time_control,
-                &game.result,
-                &game.termination,
-                &game.date,
-                &game.utc_date,
-                &game.utc_time,
-                &game.opening,
-                &game.eco,
-                &game.event,
-                &game.round,
-                &game.white,
-                &game.white_elo,
-                &game.white_rating_diff,
-                &game.white_title,
-                &game.black,
-                &game.black_elo,
-                &game.black_rating_diff,
-                &game.black_title,
+            file.write_record([
+                &game.time_control.to_string(),
+                &game.result.to_string(),
+                &game.termination.to_string(),
+                &game.utc_date.0.to_pgn(),
+                &game.utc_time.0.to_pgn(),
+                &game.opening.0.clone(),
+                &game.eco.to_string(),
+                &game.ruleset.to_event(),
+                &game.black.0.clone(),
+                &game.black_elo.to_string(),
+                &game
+                    .black_title
+                    .map(|title| title.to_string())
+                    .unwrap_or_default(),
+                &game.white.0.clone(),
+                &game.white_elo.to_string(),
+                &game
+                    .white_title
+                    .map(|title| title.to_string())
+                    .unwrap_or_default(),
+                &game.has_clock.to_string(),
+                &game.has_evaluations.to_string(),
+                &game.final_conf.to_hex(),
             ])
             .unwrap();
         }
@@ -204,49 +179,61 @@ pub fn game_csv_bench() {
         let mut file = BufWriter::new(File::create(MANUAL).unwrap());
         time = Instant::now();
 
-        file.write(b"GameId,Site,TimeControl,Result,Termination,Date,UTCDate,UTCTime,Opening,Eco,Event,Round,White,WhiteElo,WhiteRatingDiff,WhiteTitle,Black,BlackElo,BlackRatingDiff,BlackTitle").unwrap();
+        file.write_all(b"time_control,result,termination,utc_date,utc_time,opening,eco,ruleset,black,black_elo,black_title,white,white_elo,white_title,has_clock,has_evaluations,final_conf").unwrap();
 
         for _ in 0..EXECUTIONS {
-            file.write(b"\n").unwrap();
-            write!(file, "{}", game.game_id).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.site.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.time_control.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.result.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.termination.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.date.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.utc_date.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.utc_time.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.opening.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.eco.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.event.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.round.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.white.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.white_elo.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.white_rating_diff.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.white_title.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.black.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.black_elo.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.black_rating_diff.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(game.black_title.as_bytes()).unwrap();
+            file.write_all(b"\n").unwrap();
+            file.write_all(game.time_control.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.result.to_string().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.termination.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.utc_date.0.to_pgn().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.utc_time.0.to_pgn().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.opening.0.clone().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.eco.to_string().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.ruleset.to_event().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.black.0.clone().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.black_elo.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(
+                game.black_title
+                    .map(|title| title.to_string())
+                    .unwrap_or_default()
+                    .as_bytes(),
+            )
+            .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.white.0.clone().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.white_elo.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(
+                game.white_title
+                    .map(|title| title.to_string())
+                    .unwrap_or_default()
+                    .as_bytes(),
+            )
+            .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.has_clock.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.has_evaluations.to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(game.final_conf.to_hex().as_bytes()).unwrap();
         }
 
         elapsed_manual = time.elapsed();
@@ -276,7 +263,7 @@ pub fn game_csv_bench() {
         assert_eq!(csvs, csvb);
         assert_eq!(ser, csvs);
     }
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     {
         let (manual, csvb, csvs) = (
             manual_reader.headers().unwrap(),
@@ -299,7 +286,7 @@ pub fn game_csv_bench() {
         assert_eq!(csvs, csvb);
         assert_eq!(ser, csvs);
     }
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     for (manual, (csvb, csvs)) in manual_reader
         .records()
         .zip(csvb_reader.records().zip(csvs_reader.records()))
@@ -323,7 +310,7 @@ pub fn game_csv_bench() {
                 .min()
                 .unwrap()
     );
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     assert!(elapsed_manual < *[elapsed_csvb, elapsed_csvs].iter().min().unwrap());
 }
 
@@ -331,8 +318,7 @@ pub fn game_csv_bench() {
 #[test]
 pub fn move_csv_bench() {
     use csv::{Reader, Writer};
-    use lichess::data::r#move::Move;
-    use pgn_reader::Nag;
+    use lichess::{attributes::MoveDescriptor, data::r#move::Move};
     use std::{
         fs::{File, remove_file},
         io::{BufWriter, Write},
@@ -340,12 +326,10 @@ pub fn move_csv_bench() {
     };
     const EXECUTIONS: i32 = 1000000;
     let r#move: Move = Move {
-        game_id: 9000,
         num: 30,
-        san: "Nbd2".into(),
-        nag: Some(Nag::BLUNDER.0),
-        eval: "0.17".into(),
-        clk: "0:00:30".into(),
+        descriptor: MoveDescriptor::from_u32(0b01000010010101001000000100000000).unwrap(),
+        eval: Some("0.17".parse().unwrap()),
+        clk: Some("0:00:30".parse().unwrap()),
     };
     #[cfg(any(feature = "chrono-serde", feature = "time-serde"))]
     const SERIALIZED: &str = "moves_ser.tmp";
@@ -363,15 +347,7 @@ pub fn move_csv_bench() {
         let mut file = Writer::from_path(SERIALIZED).unwrap();
         time = Instant::now();
         for _ in 0..EXECUTIONS {
-            file.serialize(Move {
-                game_id: 9000,
-                num: 30,
-                san: "Nbd2".into(),
-                nag: Some(Nag::BLUNDER.0),
-                eval: "0.17".into(),
-                clk: "0:00:30".into(),
-            })
-            .unwrap();
+            file.serialize(&r#move).unwrap();
         }
 
         elapsed_serialized = time.elapsed();
@@ -380,22 +356,16 @@ pub fn move_csv_bench() {
         let mut file = Writer::from_path(CSVB).unwrap();
         time = Instant::now();
 
-        file.write_byte_record(
-            &["GameId", "Num", "San", "Nag", "Eval", "Clk"]
-                .as_slice()
-                .into(),
-        )
-        .unwrap();
+        file.write_byte_record(&["num", "descriptor", "eval", "clk"].as_slice().into())
+            .unwrap();
 
         for _ in 0..EXECUTIONS {
             file.write_byte_record(
                 &[
-                    &format!("{}", r#move.game_id),
-                    &format!("{}", r#move.num),
-                    &r#move.san,
-                    &r#move.nag.map(|nag| format!("{}", nag)).unwrap_or_default(),
-                    &r#move.eval,
-                    &r#move.clk,
+                    &r#move.num.to_string(),
+                    &r#move.descriptor.to_u32().to_string(),
+                    &r#move.eval.map(|eval| eval.to_string()).unwrap_or_default(),
+                    &r#move.clk.map(|clk| clk.0.to_pgn()).unwrap_or_default(),
                 ]
                 .as_slice()
                 .into(),
@@ -409,17 +379,15 @@ pub fn move_csv_bench() {
         let mut file = Writer::from_path(CSVS).unwrap();
         time = Instant::now();
 
-        file.write_record(&["GameId", "Num", "San", "Nag", "Eval", "Clk"])
+        file.write_record(["num", "descriptor", "eval", "clk"])
             .unwrap();
 
         for _ in 0..EXECUTIONS {
-            file.write_record(&[
-                &format!("{}", r#move.game_id),
-                &format!("{}", r#move.num),
-                &r#move.san,
-                &r#move.nag.map(|nag| format!("{}", nag)).unwrap_or_default(),
-                &r#move.eval,
-                &r#move.clk,
+            file.write_record([
+                &r#move.num.to_string(),
+                &r#move.descriptor.to_u32().to_string(),
+                &r#move.eval.map(|eval| eval.to_string()).unwrap_or_default(),
+                &r#move.clk.map(|clk| clk.0.to_pgn()).unwrap_or_default(),
             ])
             .unwrap();
         }
@@ -430,23 +398,32 @@ pub fn move_csv_bench() {
         let mut file = BufWriter::new(File::create(MANUAL).unwrap());
         time = Instant::now();
 
-        file.write(b"GameId,Num,San,Nag,Eval,Clk").unwrap();
+        file.write_all(b"num,descriptor,eval,clk").unwrap();
 
         for _ in 0..EXECUTIONS {
-            file.write(b"\n").unwrap();
-            write!(file, "{}", r#move.game_id).unwrap();
-            file.write(b",").unwrap();
-            write!(file, "{}", r#move.num).unwrap();
-            file.write(b",").unwrap();
-            file.write(r#move.san.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            if let Some(nag) = &r#move.nag {
-                write!(file, "{}", nag).unwrap();
-            }
-            file.write(b",").unwrap();
-            file.write(r#move.eval.as_bytes()).unwrap();
-            file.write(b",").unwrap();
-            file.write(r#move.clk.as_bytes()).unwrap();
+            file.write_all(b"\n").unwrap();
+            file.write_all(r#move.num.to_string().as_bytes()).unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(r#move.descriptor.to_u32().to_string().as_bytes())
+                .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(
+                r#move
+                    .eval
+                    .map(|eval| eval.to_string())
+                    .unwrap_or_default()
+                    .as_bytes(),
+            )
+            .unwrap();
+            file.write_all(b",").unwrap();
+            file.write_all(
+                r#move
+                    .clk
+                    .map(|clk| clk.0.to_pgn())
+                    .unwrap_or_default()
+                    .as_bytes(),
+            )
+            .unwrap();
         }
 
         elapsed_manual = time.elapsed();
@@ -476,7 +453,7 @@ pub fn move_csv_bench() {
         assert_eq!(csvs, csvb);
         assert_eq!(ser, csvs);
     }
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     {
         let (manual, csvb, csvs) = (
             manual_reader.headers().unwrap(),
@@ -499,7 +476,7 @@ pub fn move_csv_bench() {
         assert_eq!(csvs, csvb);
         assert_eq!(ser, csvs);
     }
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     for (manual, (csvb, csvs)) in manual_reader
         .records()
         .zip(csvb_reader.records().zip(csvs_reader.records()))
@@ -523,6 +500,6 @@ pub fn move_csv_bench() {
                 .min()
                 .unwrap()
     );
-    #[cfg(not(feature = "serde"))]
+    #[cfg(not(any(feature = "chrono-serde", feature = "time-serde")))]
     assert!(elapsed_manual < *[elapsed_csvb, elapsed_csvs].iter().min().unwrap());
 }